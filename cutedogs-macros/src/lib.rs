@@ -0,0 +1,4467 @@
+#![allow(unused_assignments)]
+#![cfg_attr(feature = "nightly", feature(proc_macro_diagnostic))]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote, parse_quote_spanned,
+    punctuated::Punctuated,
+    AttrStyle, Attribute, Block, DeriveInput, Expr, ExprLit, Field, FnArg, ForeignItem,
+    GenericArgument, GenericParam, Generics, Ident, ImplItem, Item, ItemConst, ItemEnum, ItemFn,
+    ItemImpl, ItemMod, ItemStatic, ItemStruct, ItemTrait, ItemType, ItemUnion, ItemUse, Lifetime,
+    Lit, LitBool, LitInt, LitStr, Meta, MetaNameValue, Pat, Path, PathArguments, Result,
+    ReturnType, Signature, Stmt, Token, TraitItem, Type, UseName, UseRename, UseTree, Visibility,
+    WherePredicate,
+};
+
+#[derive(Clone, Default)]
+struct DocArgs {
+    summary: Option<String>,
+    returns: Option<LitStr>,
+    errors: Option<String>,
+    error_variants: Vec<Param>,
+    params: Vec<Param>,
+    params_inherited: bool,
+    generics: Vec<Param>,
+    lifetimes: Vec<Param>,
+    fields: Vec<Param>,
+    typed_fields: Vec<(String, String)>,
+    variants: Vec<Param>,
+    methods: Vec<MethodDocs>,
+    assoc: Vec<Param>,
+    inherit: Option<LitStr>,
+    inherited: Option<Box<DocArgs>>,
+    deprecated: Option<String>,
+    deprecated_since: Option<String>,
+    deprecated_attr: Option<bool>,
+    deprecated_bare: bool,
+    must_use: Option<String>,
+    hidden: bool,
+    auto_params: bool,
+    auto_summary: bool,
+    auto_generics: bool,
+    auto_must_use: Option<bool>,
+    allow_undocumented_panics: bool,
+    strict: Option<bool>,
+    migration: Option<String>,
+    stability: Option<String>,
+    since: Option<String>,
+    msrv: Option<String>,
+    units: Option<String>,
+    param_units: Vec<Param>,
+    default_values: Vec<Param>,
+    tracking_issue: Option<String>,
+    feature: Option<String>,
+    cfg: Option<LitStr>,
+    deterministic: Option<String>,
+    idempotent: Option<String>,
+    examples: Vec<Example>,
+    tracked_files: Vec<String>,
+    tracked_env: Vec<String>,
+    panics: Option<String>,
+    side_effects: Option<String>,
+    safety: Option<String>,
+    security: Option<String>,
+    abi: Option<String>,
+    ownership: Option<String>,
+    complexity: Option<String>,
+    algorithm: Option<String>,
+    performance: Option<String>,
+    memory: Option<String>,
+    thread_safety: Option<String>,
+    locking: Option<String>,
+    blocking: Option<String>,
+    cancellation_safety: Option<LitStr>,
+    platform: Option<LitStr>,
+    see_also: Option<String>,
+    see_also_paths: Vec<Path>,
+    references: Vec<String>,
+    keywords: Vec<LitStr>,
+    history: Vec<Param>,
+    sections: Vec<Param>,
+    environment: Vec<Param>,
+    edge_cases: Vec<String>,
+    invariants: Option<String>,
+    preconditions: Option<String>,
+    postconditions: Option<String>,
+    warning: Option<String>,
+    todo: Option<String>,
+    notes: Vec<String>,
+    authors: Option<String>,
+    is_unimplemented: bool,
+    unimplemented_reason: Option<String>,
+    todo_body: Option<String>,
+    example_use_crate: Option<String>,
+    warnings: Vec<Warning>,
+    spans: Vec<(String, proc_macro2::Span)>,
+    merge: Option<String>,
+    handwritten: Vec<Attribute>,
+    signature: Option<Signature>,
+    item_generics: Option<Generics>,
+    self_ty: Option<Type>,
+    auto_example: bool,
+    stub_default: bool,
+}
+
+impl DocArgs {
+    fn inherit(&mut self, defaults: &DocArgs) {
+        fill(&mut self.deprecated, &defaults.deprecated);
+        fill(&mut self.deprecated_since, &defaults.deprecated_since);
+        fill(&mut self.deprecated_attr, &defaults.deprecated_attr);
+        self.deprecated_bare |= defaults.deprecated_bare;
+        fill(&mut self.migration, &defaults.migration);
+        fill(&mut self.stability, &defaults.stability);
+        fill(&mut self.since, &defaults.since);
+        fill(&mut self.msrv, &defaults.msrv);
+        fill(&mut self.panics, &defaults.panics);
+        fill(&mut self.safety, &defaults.safety);
+        fill(&mut self.abi, &defaults.abi);
+        fill(&mut self.ownership, &defaults.ownership);
+        if self.see_also.is_none() {
+            self.see_also_paths.clone_from(&defaults.see_also_paths);
+        }
+        fill(&mut self.see_also, &defaults.see_also);
+        fill(&mut self.invariants, &defaults.invariants);
+        fill(&mut self.thread_safety, &defaults.thread_safety);
+        fill(&mut self.platform, &defaults.platform);
+        fill(&mut self.authors, &defaults.authors);
+        fill(&mut self.warning, &defaults.warning);
+        fill(&mut self.locking, &defaults.locking);
+        fill(&mut self.security, &defaults.security);
+        fill(&mut self.strict, &defaults.strict);
+        fill(&mut self.merge, &defaults.merge);
+        self.auto_params |= defaults.auto_params;
+        self.auto_summary |= defaults.auto_summary;
+        self.auto_generics |= defaults.auto_generics;
+        fill(&mut self.auto_must_use, &defaults.auto_must_use);
+        self.allow_undocumented_panics |= defaults.allow_undocumented_panics;
+        fill(&mut self.example_use_crate, &defaults.example_use_crate);
+        if self.notes.is_empty() {
+            self.notes.clone_from(&defaults.notes);
+        }
+    }
+
+    fn span_of(&self, key: &str) -> proc_macro2::Span {
+        self.spans
+            .iter()
+            .find(|(field, _)| field == key)
+            .map_or_else(proc_macro2::Span::call_site, |(_, span)| *span)
+    }
+
+    fn inherit_all(&mut self, base: &DocArgs) {
+        self.inherit(base);
+        fill(&mut self.summary, &base.summary);
+        fill(&mut self.returns, &base.returns);
+        fill(&mut self.units, &base.units);
+        fill(&mut self.tracking_issue, &base.tracking_issue);
+        fill(&mut self.feature, &base.feature);
+        fill(&mut self.cfg, &base.cfg);
+        fill(&mut self.deterministic, &base.deterministic);
+        fill(&mut self.idempotent, &base.idempotent);
+        fill(&mut self.errors, &base.errors);
+        fill(&mut self.complexity, &base.complexity);
+        fill(&mut self.cancellation_safety, &base.cancellation_safety);
+        fill(&mut self.preconditions, &base.preconditions);
+        fill(&mut self.postconditions, &base.postconditions);
+        fill(&mut self.performance, &base.performance);
+        fill(&mut self.memory, &base.memory);
+        fill(&mut self.blocking, &base.blocking);
+        fill(&mut self.side_effects, &base.side_effects);
+        fill(&mut self.algorithm, &base.algorithm);
+        if self.error_variants.is_empty() {
+            self.error_variants.clone_from(&base.error_variants);
+        }
+        if self.references.is_empty() {
+            self.references.clone_from(&base.references);
+        }
+        if self.history.is_empty() {
+            self.history.clone_from(&base.history);
+        }
+        if self.examples.is_empty() {
+            self.examples.clone_from(&base.examples);
+        }
+        if self.edge_cases.is_empty() {
+            self.edge_cases.clone_from(&base.edge_cases);
+        }
+        if self.environment.is_empty() {
+            self.environment.clone_from(&base.environment);
+        }
+        if self.sections.is_empty() {
+            self.sections.clone_from(&base.sections);
+        }
+        if self.keywords.is_empty() {
+            self.keywords.clone_from(&base.keywords);
+        }
+        if self.params.is_empty() {
+            self.params.clone_from(&base.params);
+            self.params_inherited = true;
+        }
+        if self.param_units.is_empty() {
+            self.param_units.clone_from(&base.param_units);
+        }
+        if self.default_values.is_empty() {
+            self.default_values.clone_from(&base.default_values);
+        }
+        if self.generics.is_empty() {
+            self.generics.clone_from(&base.generics);
+        }
+        if self.lifetimes.is_empty() {
+            self.lifetimes.clone_from(&base.lifetimes);
+        }
+        if !self.is_unimplemented {
+            self.is_unimplemented = base.is_unimplemented;
+            self.unimplemented_reason
+                .clone_from(&base.unimplemented_reason);
+        }
+    }
+}
+
+fn fill<T: Clone>(field: &mut Option<T>, default: &Option<T>) {
+    if field.is_none() {
+        field.clone_from(default);
+    }
+}
+
+const FIELDS: &[&str] = &[
+    "summary",
+    "returns",
+    "errors",
+    "deprecated",
+    "deprecated_since",
+    "deprecated_attr",
+    "must_use",
+    "hidden",
+    "strict",
+    "auto_params",
+    "auto_summary",
+    "auto_generics",
+    "auto_must_use",
+    "allow_undocumented_panics",
+    "todo_body",
+    "stub",
+    "unimplemented",
+    "migration",
+    "stability",
+    "since",
+    "msrv",
+    "units",
+    "defaults",
+    "tracking_issue",
+    "feature",
+    "cfg",
+    "deterministic",
+    "idempotent",
+    "example",
+    "example_no_run",
+    "example_ignore",
+    "example_should_panic",
+    "example_file",
+    "example_use_crate",
+    "merge",
+    "examples",
+    "panics",
+    "side_effects",
+    "safety",
+    "security",
+    "abi",
+    "ownership",
+    "complexity",
+    "algorithm",
+    "performance",
+    "memory",
+    "thread_safety",
+    "locking",
+    "blocking",
+    "cancellation_safety",
+    "platform",
+    "see_also",
+    "references",
+    "history",
+    "sections",
+    "environment",
+    "edge_cases",
+    "keywords",
+    "invariants",
+    "preconditions",
+    "postconditions",
+    "warning",
+    "todo",
+    "note",
+    "authors",
+    "params",
+    "generics",
+    "lifetimes",
+    "fields",
+    "variants",
+    "assoc",
+    "methods",
+    "inherit",
+];
+
+const NOT_MARKDOWN: &[&str] = &[
+    "example",
+    "example_no_run",
+    "example_ignore",
+    "example_should_panic",
+    "example_file",
+    "examples",
+    "cfg",
+];
+
+const PRIMITIVES: &[&str] = &[
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "f32",
+    "f64",
+    "bool",
+    "char",
+    "str",
+    "slice",
+    "array",
+    "tuple",
+    "pointer",
+    "reference",
+    "fn",
+    "never",
+    "unit",
+];
+
+const MULTILINE: &[&str] = &[
+    "summary",
+    "returns",
+    "errors",
+    "deprecated",
+    "migration",
+    "panics",
+    "side_effects",
+    "safety",
+    "security",
+    "ownership",
+    "complexity",
+    "algorithm",
+    "performance",
+    "memory",
+    "thread_safety",
+    "locking",
+    "blocking",
+    "cancellation_safety",
+    "invariants",
+    "preconditions",
+    "postconditions",
+    "warning",
+    "todo",
+];
+
+const REPEATABLE: &[&str] = &[
+    "note",
+    "example",
+    "example_no_run",
+    "example_ignore",
+    "example_should_panic",
+    "example_file",
+];
+
+impl Parse for DocArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut deps = Dependencies::default();
+        let tokens = expand_value_macros(input.parse()?, &mut deps)?;
+        let mut args = syn::parse::Parser::parse2(DocArgs::parse_fields, tokens)?;
+        args.tracked_files.extend(deps.files);
+        args.tracked_env.extend(deps.env);
+        Ok(args)
+    }
+}
+
+impl DocArgs {
+    fn parse_fields(input: ParseStream) -> Result<Self> {
+        let mut args = DocArgs::default();
+        let mut migration = None;
+        let mut versions = (None, None);
+        let mut fields = Vec::new();
+        let mut keys: Vec<Ident> = Vec::new();
+        while !input.is_empty() {
+            let key = input.fork().parse::<Ident>()?;
+            if keys.contains(&key) && !REPEATABLE.contains(&key.to_string().as_str()) {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("duplicate field `{}`", key),
+                ));
+            }
+            let value = input.fork();
+            value.parse::<Ident>()?;
+            if value.peek(Token![=]) {
+                value.parse::<Token![=]>()?;
+            }
+            args.spans.push((key.to_string(), value.span()));
+            let mut literals = Vec::new();
+            let mut first_line = None;
+            if MULTILINE.contains(&key.to_string().as_str()) && value.peek(syn::token::Bracket) {
+                let (lines, span) = parse_lines(&value)?;
+                first_line = lines.first().cloned();
+                literals.push(join_lines(&lines, span));
+            } else if key != "methods" && key != "__inherited" {
+                while !value.is_empty() && !value.peek(Token![,]) {
+                    string_literals(value.parse()?, &mut literals);
+                }
+            }
+            for lit in &literals {
+                if lit.value().trim().is_empty() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("empty value for `{}`", key),
+                    ));
+                }
+                if !NOT_MARKDOWN.contains(&key.to_string().as_str()) {
+                    lint_markdown(lit, &mut args.warnings);
+                }
+            }
+            if key == "summary" {
+                if let Some(summary) = first_line.as_ref().or(literals.first()) {
+                    lint_summary(summary, &mut args.warnings);
+                }
+            }
+            fields.push(input.parse::<FieldValue>()?);
+            keys.push(key);
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        for field in fields {
+            match field {
+                FieldValue::Summary(val) => args.summary = Some(val.value()),
+                FieldValue::Returns(val) => args.returns = Some(val),
+                FieldValue::Errors(val) => args.errors = Some(val.value()),
+                FieldValue::ErrorVariants(variants) => args.error_variants = variants,
+                FieldValue::Deprecated(val) => args.deprecated = Some(val.value()),
+                FieldValue::DeprecatedSince(val) => {
+                    versions.1 = Some((check_semver(&val)?, val.span()));
+                    args.deprecated_since = Some(val.value());
+                }
+                FieldValue::DeprecatedAttr(val) => args.deprecated_attr = Some(val.value),
+                FieldValue::MustUse(val) => args.must_use = Some(val.value()),
+                FieldValue::Hidden(val) => args.hidden = val.value,
+                FieldValue::AutoParams(val) => args.auto_params = val.value,
+                FieldValue::AutoSummary(val) => args.auto_summary = val.value,
+                FieldValue::AutoGenerics(val) => args.auto_generics = val.value,
+                FieldValue::AutoMustUse(val) => args.auto_must_use = Some(val.value),
+                FieldValue::AllowUndocumentedPanics(val) => {
+                    args.allow_undocumented_panics = val.value
+                }
+                FieldValue::Strict(val) => args.strict = Some(val.value),
+                FieldValue::TodoBody(val) => args.todo_body = Some(val.value()),
+                FieldValue::Unknown(key) => {
+                    let message = match suggest_field(&key.to_string()) {
+                        Some(field) => {
+                            format!("unknown field `{}` ignored, did you mean `{}`?", key, field)
+                        }
+                        None => format!("unknown field `{}` ignored", key),
+                    };
+                    args.warnings.push(Warning {
+                        span: key.span(),
+                        message,
+                    });
+                }
+                FieldValue::Merge(val) => {
+                    if !matches!(
+                        val.value().as_str(),
+                        "prepend" | "append" | "replace" | "error"
+                    ) {
+                        return Err(syn::Error::new(
+                            val.span(),
+                            "expected one of: \"prepend\", \"append\", \"replace\", \"error\"",
+                        ));
+                    }
+                    args.merge = Some(val.value());
+                }
+                FieldValue::Unimplemented(reason) => {
+                    args.is_unimplemented = true;
+                    args.unimplemented_reason = reason.as_ref().map(LitStr::value);
+                }
+                FieldValue::ExampleUseCrate(val) => args.example_use_crate = Some(val.value()),
+                FieldValue::Stub(val) => {
+                    if val.value() != "default" {
+                        return Err(syn::Error::new(val.span(), "expected \"default\""));
+                    }
+                    args.stub_default = true;
+                }
+                FieldValue::Migration(val) => migration = Some(val),
+                FieldValue::Since(val) => {
+                    let since = check_semver(&val)?;
+                    check_since(&val, &since)?;
+                    versions.0 = Some(since);
+                    args.since = Some(val.value());
+                }
+                FieldValue::Stability(val) => {
+                    if !matches!(val.value().as_str(), "experimental" | "unstable" | "stable") {
+                        return Err(syn::Error::new(
+                            val.span(),
+                            "expected one of: \"experimental\", \"unstable\", \"stable\"",
+                        ));
+                    }
+                    args.stability = Some(val.value());
+                }
+                FieldValue::Msrv(val) => {
+                    check_msrv(&val)?;
+                    args.msrv = Some(val.value());
+                }
+                FieldValue::Units(val) => args.units = Some(val.value()),
+                FieldValue::ParamUnits(units) => args.param_units = units,
+                FieldValue::Defaults(defaults) => args.default_values = defaults,
+                FieldValue::TrackingIssue(val) => args.tracking_issue = Some(val.value()),
+                FieldValue::Feature(val) => args.feature = Some(val.value()),
+                FieldValue::Cfg(val) => {
+                    val.parse::<Meta>().map_err(|err| {
+                        syn::Error::new(val.span(), format!("invalid `cfg` predicate: {}", err))
+                    })?;
+                    args.cfg = Some(val);
+                }
+                FieldValue::Deterministic(val) => args.deterministic = Some(val.value()),
+                FieldValue::Idempotent(val) => args.idempotent = Some(val.value()),
+                FieldValue::AutoExample => args.auto_example = true,
+                FieldValue::Example(fence, val) => {
+                    if fence != "ignore" {
+                        check_example(&val.value(), val.span())?;
+                    }
+                    args.examples.push(Example {
+                        title: None,
+                        fence,
+                        code: val.value(),
+                        generated: false,
+                    })
+                }
+                FieldValue::Examples(examples) => {
+                    for code in &examples {
+                        check_example(&code.value(), code.span())?;
+                    }
+                    args.examples.extend(examples.iter().map(|code| Example {
+                        title: None,
+                        fence: "rust",
+                        code: code.value(),
+                        generated: false,
+                    }))
+                }
+                FieldValue::ExampleFile(path) => {
+                    let (full_path, code) = read_example_file(&path)?;
+                    check_example(&code, path.span())?;
+                    args.examples.push(Example {
+                        title: None,
+                        fence: "rust",
+                        code,
+                        generated: false,
+                    });
+                    args.tracked_files.push(full_path);
+                }
+                FieldValue::TitledExamples(examples) => {
+                    for example in &examples {
+                        check_example(&example.desc.value(), example.desc.span())?;
+                    }
+                    args.examples.extend(examples.iter().map(|example| Example {
+                        title: Some(example.name.value()),
+                        fence: "rust",
+                        code: example.desc.value(),
+                        generated: false,
+                    }))
+                }
+                FieldValue::Panics(val) => args.panics = Some(val.value()),
+                FieldValue::SideEffects(val) => args.side_effects = Some(val.value()),
+                FieldValue::Safety(val) => args.safety = Some(val.value()),
+                FieldValue::Security(val) => args.security = Some(val.value()),
+                FieldValue::Abi(val) => args.abi = Some(val.value()),
+                FieldValue::Ownership(val) => args.ownership = Some(val.value()),
+                FieldValue::Complexity(val) => args.complexity = Some(val.value()),
+                FieldValue::Algorithm(val) => args.algorithm = Some(val.value()),
+                FieldValue::Performance(val) => args.performance = Some(val.value()),
+                FieldValue::Memory(val) => args.memory = Some(val.value()),
+                FieldValue::ThreadSafety(val) => args.thread_safety = Some(val.value()),
+                FieldValue::Locking(val) => args.locking = Some(val.value()),
+                FieldValue::Blocking(val) => args.blocking = Some(val.value()),
+                FieldValue::CancellationSafety(val) => args.cancellation_safety = Some(val),
+                FieldValue::Platform(val) => args.platform = Some(val),
+                FieldValue::SeeAlso(val) => {
+                    args.see_also_paths = val
+                        .parse_with(parse_see_also)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|path| {
+                            let first = path.segments[0].ident.to_string();
+                            first != "Self" && !PRIMITIVES.contains(&first.as_str())
+                        })
+                        .collect();
+                    args.see_also = Some(val.value());
+                }
+                FieldValue::References(references) => {
+                    args.references = references.iter().map(LitStr::value).collect()
+                }
+                FieldValue::History(history) => args.history = history,
+                FieldValue::Sections(sections) => args.sections = sections,
+                FieldValue::Environment(environment) => args.environment = environment,
+                FieldValue::EdgeCases(edge_cases) => {
+                    args.edge_cases = edge_cases.iter().map(LitStr::value).collect()
+                }
+                FieldValue::Keywords(keywords) => {
+                    args.keywords = keywords
+                        .iter()
+                        .flat_map(|keywords| {
+                            keywords
+                                .value()
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|keyword| !keyword.is_empty())
+                                .map(|keyword| LitStr::new(keyword, keywords.span()))
+                                .collect::<Vec<_>>()
+                        })
+                        .collect()
+                }
+                FieldValue::Invariants(val) => args.invariants = Some(val.value()),
+                FieldValue::Preconditions(val) => args.preconditions = Some(val.value()),
+                FieldValue::Postconditions(val) => args.postconditions = Some(val.value()),
+                FieldValue::Warning(val) => args.warning = Some(val.value()),
+                FieldValue::Todo(val) => args.todo = Some(val.value()),
+                FieldValue::Note(notes) => args.notes.extend(notes.iter().map(LitStr::value)),
+                FieldValue::Authors(val) => args.authors = Some(val.value()),
+                FieldValue::Params(params) => args.params = params,
+                FieldValue::Generics(generics) => args.generics = generics,
+                FieldValue::Lifetimes(lifetimes) => args.lifetimes = lifetimes,
+                FieldValue::Fields(fields) => args.fields = fields,
+                FieldValue::Variants(variants) => args.variants = variants,
+                FieldValue::Methods(methods) => args.methods = methods,
+                FieldValue::Assoc(assoc) => args.assoc = assoc,
+                FieldValue::Inherit(path) => args.inherit = Some(path),
+                FieldValue::Inherited(base) => args.inherited = Some(base),
+            }
+        }
+
+        if args.is_unimplemented {
+            let conflict = keys.iter().find_map(|key| {
+                let reason = match key.to_string().as_str() {
+                    "todo_body" | "stub" => "both replace the function body",
+                    "example" | "example_no_run" | "example_file" | "examples" => {
+                        "the example would only demonstrate a panic"
+                    }
+                    _ => return None,
+                };
+                Some((key, reason))
+            });
+            if let Some((key, reason)) = conflict {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!(
+                        "`unimplemented` cannot be combined with `{}`: {}",
+                        key, reason
+                    ),
+                ));
+            }
+        }
+        if let (Some(since), Some((deprecated_since, span))) = versions {
+            if deprecated_since < since {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "deprecated in {} but only available since {}",
+                        args.deprecated_since.as_deref().unwrap_or_default(),
+                        args.since.as_deref().unwrap_or_default()
+                    ),
+                ));
+            }
+        }
+        if let Some(migration) = migration {
+            if args.deprecated.is_none() && args.deprecated_since.is_none() {
+                return Err(syn::Error::new(
+                    migration.span(),
+                    "`migration` requires `deprecated` or `deprecated_since`",
+                ));
+            }
+            args.migration = Some(migration.value());
+        }
+        Ok(args)
+    }
+}
+
+enum FieldValue {
+    Summary(LitStr),
+    Returns(LitStr),
+    Errors(LitStr),
+    ErrorVariants(Vec<Param>),
+    Params(Vec<Param>),
+    Generics(Vec<Param>),
+    Lifetimes(Vec<Param>),
+    Fields(Vec<Param>),
+    Variants(Vec<Param>),
+    Methods(Vec<MethodDocs>),
+    Assoc(Vec<Param>),
+    Inherit(LitStr),
+    Inherited(Box<DocArgs>),
+    Deprecated(LitStr),
+    DeprecatedSince(LitStr),
+    DeprecatedAttr(LitBool),
+    MustUse(LitStr),
+    Hidden(LitBool),
+    AutoParams(LitBool),
+    AutoSummary(LitBool),
+    AutoGenerics(LitBool),
+    AutoMustUse(LitBool),
+    AllowUndocumentedPanics(LitBool),
+    Strict(LitBool),
+    TodoBody(LitStr),
+    Unimplemented(Option<LitStr>),
+    Unknown(Ident),
+    Merge(LitStr),
+    ExampleUseCrate(LitStr),
+    Stub(LitStr),
+    Migration(LitStr),
+    Stability(LitStr),
+    Since(LitStr),
+    Msrv(LitStr),
+    Units(LitStr),
+    ParamUnits(Vec<Param>),
+    Defaults(Vec<Param>),
+    TrackingIssue(LitStr),
+    Feature(LitStr),
+    Cfg(LitStr),
+    Deterministic(LitStr),
+    Idempotent(LitStr),
+    Example(&'static str, LitStr),
+    AutoExample,
+    Examples(Vec<LitStr>),
+    TitledExamples(Vec<Param>),
+    ExampleFile(LitStr),
+    Panics(LitStr),
+    SideEffects(LitStr),
+    Safety(LitStr),
+    Security(LitStr),
+    Abi(LitStr),
+    Ownership(LitStr),
+    Complexity(LitStr),
+    Algorithm(LitStr),
+    Performance(LitStr),
+    Memory(LitStr),
+    ThreadSafety(LitStr),
+    Locking(LitStr),
+    Blocking(LitStr),
+    CancellationSafety(LitStr),
+    Platform(LitStr),
+    SeeAlso(LitStr),
+    References(Vec<LitStr>),
+    History(Vec<Param>),
+    Sections(Vec<Param>),
+    Environment(Vec<Param>),
+    EdgeCases(Vec<LitStr>),
+    Keywords(Vec<LitStr>),
+    Invariants(LitStr),
+    Preconditions(LitStr),
+    Postconditions(LitStr),
+    Warning(LitStr),
+    Todo(LitStr),
+    Note(Vec<LitStr>),
+    Authors(LitStr),
+}
+
+impl Parse for FieldValue {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key: Ident = input.parse()?;
+        if key == "inherit" && !input.peek(Token![=]) {
+            return Ok(FieldValue::Inherit(LitStr::new("", key.span())));
+        }
+        if key == "must_use" && !input.peek(Token![=]) {
+            return Ok(FieldValue::MustUse(LitStr::new("", key.span())));
+        }
+        if key == "hidden" && !input.peek(Token![=]) {
+            return Ok(FieldValue::Hidden(LitBool::new(true, key.span())));
+        }
+        if key == "auto_params" && !input.peek(Token![=]) {
+            return Ok(FieldValue::AutoParams(LitBool::new(true, key.span())));
+        }
+        if key == "auto_summary" && !input.peek(Token![=]) {
+            return Ok(FieldValue::AutoSummary(LitBool::new(true, key.span())));
+        }
+        if key == "auto_generics" && !input.peek(Token![=]) {
+            return Ok(FieldValue::AutoGenerics(LitBool::new(true, key.span())));
+        }
+        if key == "auto_must_use" && !input.peek(Token![=]) {
+            return Ok(FieldValue::AutoMustUse(LitBool::new(true, key.span())));
+        }
+        if key == "allow_undocumented_panics" && !input.peek(Token![=]) {
+            return Ok(FieldValue::AllowUndocumentedPanics(LitBool::new(
+                true,
+                key.span(),
+            )));
+        }
+        if key == "strict" && !input.peek(Token![=]) {
+            return Ok(FieldValue::Strict(LitBool::new(true, key.span())));
+        }
+        if key == "example_use_crate" && !input.peek(Token![=]) {
+            return Ok(FieldValue::ExampleUseCrate(LitStr::new("", key.span())));
+        }
+        if key == "todo_body" && !input.peek(Token![=]) {
+            return Ok(FieldValue::TodoBody(LitStr::new("", key.span())));
+        }
+        if key == "unimplemented" && !input.peek(Token![=]) {
+            return Ok(FieldValue::Unimplemented(None));
+        }
+        if key != "__inherited" && !FIELDS.contains(&key.to_string().as_str()) {
+            if config("CUTEDOGS_UNKNOWN_FIELDS").as_deref() != Some("warn") {
+                return Err(unknown_field(&key));
+            }
+            while !input.is_empty() && !input.peek(Token![,]) {
+                input.parse::<proc_macro2::TokenTree>()?;
+            }
+            return Ok(FieldValue::Unknown(key));
+        }
+        input.parse::<Token![=]>()?;
+
+        match key.to_string().as_str() {
+            "summary" => Ok(FieldValue::Summary(parse_text(input)?)),
+            "returns" => Ok(FieldValue::Returns(parse_text(input)?)),
+            "errors" if input.peek(syn::token::Brace) => {
+                Ok(FieldValue::ErrorVariants(parse_map(input)?))
+            }
+            "errors" => Ok(FieldValue::Errors(parse_text(input)?)),
+            "deprecated" => Ok(FieldValue::Deprecated(parse_text(input)?)),
+            "deprecated_since" => Ok(FieldValue::DeprecatedSince(input.parse()?)),
+            "deprecated_attr" => Ok(FieldValue::DeprecatedAttr(input.parse()?)),
+            "must_use" => Ok(FieldValue::MustUse(input.parse()?)),
+            "hidden" => Ok(FieldValue::Hidden(input.parse()?)),
+            "strict" => Ok(FieldValue::Strict(input.parse()?)),
+            "auto_params" => Ok(FieldValue::AutoParams(input.parse()?)),
+            "auto_summary" => Ok(FieldValue::AutoSummary(input.parse()?)),
+            "auto_generics" => Ok(FieldValue::AutoGenerics(input.parse()?)),
+            "auto_must_use" => Ok(FieldValue::AutoMustUse(input.parse()?)),
+            "allow_undocumented_panics" => Ok(FieldValue::AllowUndocumentedPanics(input.parse()?)),
+            "todo_body" => Ok(FieldValue::TodoBody(input.parse()?)),
+            "unimplemented" => Ok(FieldValue::Unimplemented(Some(input.parse()?))),
+            "stub" => Ok(FieldValue::Stub(input.parse()?)),
+            "migration" => Ok(FieldValue::Migration(parse_text(input)?)),
+            "stability" => Ok(FieldValue::Stability(input.parse()?)),
+            "since" if input.peek(Ident) => {
+                let auto = input.parse::<Ident>()?;
+                if auto != "auto" {
+                    return Err(syn::Error::new(
+                        auto.span(),
+                        "expected a version string or `auto`",
+                    ));
+                }
+                match std::env::var("CARGO_PKG_VERSION") {
+                    Ok(version) if !version.is_empty() => {
+                        Ok(FieldValue::Since(LitStr::new(&version, auto.span())))
+                    }
+                    _ => Err(syn::Error::new(
+                        auto.span(),
+                        "`since = auto` requires `CARGO_PKG_VERSION`, which is only set when building with cargo",
+                    )),
+                }
+            }
+            "since" => Ok(FieldValue::Since(input.parse()?)),
+            "msrv" => Ok(FieldValue::Msrv(input.parse()?)),
+            "units" if input.peek(syn::token::Brace) => {
+                Ok(FieldValue::ParamUnits(parse_map(input)?))
+            }
+            "units" => Ok(FieldValue::Units(input.parse()?)),
+            "defaults" => Ok(FieldValue::Defaults(parse_map(input)?)),
+            "tracking_issue" => Ok(FieldValue::TrackingIssue(input.parse()?)),
+            "feature" => Ok(FieldValue::Feature(input.parse()?)),
+            "cfg" => Ok(FieldValue::Cfg(input.parse()?)),
+            "deterministic" => Ok(FieldValue::Deterministic(input.parse()?)),
+            "idempotent" => Ok(FieldValue::Idempotent(input.parse()?)),
+            "example" if input.peek(Ident) => {
+                let auto = input.parse::<Ident>()?;
+                if auto != "auto" {
+                    return Err(syn::Error::new(
+                        auto.span(),
+                        "expected an example string or `auto`",
+                    ));
+                }
+                Ok(FieldValue::AutoExample)
+            }
+            "example" => Ok(FieldValue::Example("rust", input.parse()?)),
+            "example_no_run" => Ok(FieldValue::Example("no_run", input.parse()?)),
+            "example_ignore" => Ok(FieldValue::Example("ignore", input.parse()?)),
+            "example_should_panic" => Ok(FieldValue::Example("should_panic", input.parse()?)),
+            "example_file" => Ok(FieldValue::ExampleFile(input.parse()?)),
+            "example_use_crate" => Ok(FieldValue::ExampleUseCrate(input.parse()?)),
+            "merge" => Ok(FieldValue::Merge(input.parse()?)),
+            "examples" if input.peek(syn::token::Brace) => {
+                Ok(FieldValue::TitledExamples(parse_map(input)?))
+            }
+            "examples" => Ok(FieldValue::Examples(parse_list(input)?)),
+            "panics" => Ok(FieldValue::Panics(parse_text(input)?)),
+            "side_effects" => Ok(FieldValue::SideEffects(parse_text(input)?)),
+            "safety" => Ok(FieldValue::Safety(parse_text(input)?)),
+            "security" => Ok(FieldValue::Security(parse_text(input)?)),
+            "abi" => Ok(FieldValue::Abi(input.parse()?)),
+            "ownership" => Ok(FieldValue::Ownership(parse_text(input)?)),
+            "complexity" => Ok(FieldValue::Complexity(parse_text(input)?)),
+            "algorithm" => Ok(FieldValue::Algorithm(parse_text(input)?)),
+            "performance" => Ok(FieldValue::Performance(parse_text(input)?)),
+            "memory" => Ok(FieldValue::Memory(parse_text(input)?)),
+            "thread_safety" => Ok(FieldValue::ThreadSafety(parse_text(input)?)),
+            "locking" => Ok(FieldValue::Locking(parse_text(input)?)),
+            "blocking" => Ok(FieldValue::Blocking(parse_text(input)?)),
+            "cancellation_safety" => Ok(FieldValue::CancellationSafety(parse_text(input)?)),
+            "platform" => Ok(FieldValue::Platform(input.parse()?)),
+            "see_also" => Ok(FieldValue::SeeAlso(input.parse()?)),
+            "references" => Ok(FieldValue::References(parse_list(input)?)),
+            "history" => Ok(FieldValue::History(parse_map(input)?)),
+            "sections" => Ok(FieldValue::Sections(parse_map(input)?)),
+            "environment" => Ok(FieldValue::Environment(parse_map(input)?)),
+            "edge_cases" => Ok(FieldValue::EdgeCases(parse_list(input)?)),
+            "keywords" => Ok(FieldValue::Keywords(parse_list(input)?)),
+            "invariants" => Ok(FieldValue::Invariants(parse_text(input)?)),
+            "preconditions" => Ok(FieldValue::Preconditions(parse_text(input)?)),
+            "postconditions" => Ok(FieldValue::Postconditions(parse_text(input)?)),
+            "warning" => Ok(FieldValue::Warning(parse_text(input)?)),
+            "todo" => Ok(FieldValue::Todo(parse_text(input)?)),
+            "note" => Ok(FieldValue::Note(parse_list(input)?)),
+            "authors" => Ok(FieldValue::Authors(input.parse()?)),
+            "params" => Ok(FieldValue::Params(parse_map(input)?)),
+            "generics" => Ok(FieldValue::Generics(parse_map(input)?)),
+            "lifetimes" => Ok(FieldValue::Lifetimes(parse_map(input)?)),
+            "fields" => Ok(FieldValue::Fields(parse_map(input)?)),
+            "variants" => Ok(FieldValue::Variants(parse_map(input)?)),
+            "assoc" => Ok(FieldValue::Assoc(parse_map(input)?)),
+            "methods" => {
+                let content;
+                syn::braced!(content in input);
+                let entries = Punctuated::<MethodDocs, Token![,]>::parse_terminated(&content)?;
+                Ok(FieldValue::Methods(entries.into_iter().collect()))
+            }
+            "inherit" => Ok(FieldValue::Inherit(input.parse()?)),
+            "__inherited" => {
+                let content;
+                syn::braced!(content in input);
+                Ok(FieldValue::Inherited(Box::new(content.parse()?)))
+            }
+            _ => Err(unknown_field(&key)),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Example {
+    title: Option<String>,
+    fence: &'static str,
+    code: String,
+    generated: bool,
+}
+
+#[derive(Clone)]
+struct Param {
+    name: LitStr,
+    desc: LitStr,
+}
+
+impl Parse for Param {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = if input.peek(LitStr) {
+            input.parse()?
+        } else if input.peek(Lifetime) {
+            let lifetime: Lifetime = input.parse()?;
+            LitStr::new(&lifetime.to_string(), lifetime.span())
+        } else if input.peek(LitInt) {
+            let index: LitInt = input.parse()?;
+            LitStr::new(&index.to_string(), index.span())
+        } else {
+            let ident: Ident = input.parse()?;
+            LitStr::new(&ident.to_string(), ident.span())
+        };
+        input.parse::<Token![:]>()?;
+        let desc = input.parse()?;
+        Ok(Param { name, desc })
+    }
+}
+
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Semver {
+    core: (u64, u64, u64),
+    pre: Prerelease,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Prerelease {
+    Pre(Vec<std::result::Result<u64, String>>),
+    Release,
+}
+
+fn parse_semver(version: &str) -> Option<Semver> {
+    let version = version.split('+').next()?;
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let mut parts = core.split('.').map(|part| {
+        let leading_zero = part.len() > 1 && part.starts_with('0');
+        part.parse::<u64>().ok().filter(|_| !leading_zero)
+    });
+    let core = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    let pre = match pre {
+        Some(pre) => Prerelease::Pre(
+            pre.split('.')
+                .map(|ident| match ident.parse::<u64>() {
+                    _ if ident.is_empty() => None,
+                    Ok(number) => Some(Ok(number)),
+                    Err(_) => ident
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+                        .then(|| Err(ident.to_string())),
+                })
+                .collect::<Option<_>>()?,
+        ),
+        None => Prerelease::Release,
+    };
+    Some(Semver { core, pre })
+}
+
+fn check_semver(version: &LitStr) -> Result<Semver> {
+    parse_semver(&version.value()).ok_or_else(|| {
+        syn::Error::new(
+            version.span(),
+            format!(
+                "`{}` is not a valid semver version, expected something like `1.2.0`",
+                version.value()
+            ),
+        )
+    })
+}
+
+fn check_since(lit: &LitStr, since: &Semver) -> Result<()> {
+    let crate_version = std::env::var("CARGO_PKG_VERSION").unwrap_or_default();
+    let Some(current) = parse_semver(&crate_version) else {
+        return Ok(());
+    };
+    if !since_allowed(since, &current) {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!(
+                "`since = \"{}\"` is newer than this crate's version ({}); at most the next minor release may be announced",
+                lit.value(),
+                crate_version
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn since_allowed(since: &Semver, current: &Semver) -> bool {
+    let (major, minor, _) = current.core;
+    since <= current || since.core <= (major, minor + 1, 0)
+}
+
+fn check_msrv(msrv: &LitStr) -> Result<()> {
+    let Some(version) = parse_version(&msrv.value()) else {
+        return Err(syn::Error::new(
+            msrv.span(),
+            "expected an MSRV of the form `1.70` or `1.70.0`",
+        ));
+    };
+
+    let crate_msrv = config("CUTEDOGS_MSRV")
+        .or_else(|| std::env::var("CARGO_PKG_RUST_VERSION").ok())
+        .unwrap_or_default();
+    if let Some(crate_version) = parse_version(&crate_msrv) {
+        if version < crate_version {
+            return Err(syn::Error::new(
+                msrv.span(),
+                format!(
+                    "MSRV {} is older than the crate-wide MSRV {}",
+                    msrv.value(),
+                    crate_msrv
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+struct MethodDocs {
+    name: Ident,
+    args: DocArgs,
+    tokens: TokenStream2,
+}
+
+impl Parse for MethodDocs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let content;
+        syn::braced!(content in input);
+        let tokens = content.fork().parse()?;
+        let args = content.parse()?;
+        Ok(MethodDocs { name, args, tokens })
+    }
+}
+
+fn read_example_file(path: &LitStr) -> Result<(String, String)> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = std::path::Path::new(&manifest_dir).join(path.value());
+    match std::fs::read_to_string(&full_path) {
+        Ok(code) => Ok((
+            full_path.to_string_lossy().into_owned(),
+            code.trim_end().to_string(),
+        )),
+        Err(err) => Err(syn::Error::new(
+            path.span(),
+            format!("couldn't read `{}`: {}", full_path.display(), err),
+        )),
+    }
+}
+
+fn check_example(code: &str, span: proc_macro2::Span) -> Result<()> {
+    let code = code
+        .lines()
+        .map(|line| match line.trim_start() {
+            "#" => "",
+            trimmed => trimmed.strip_prefix("# ").unwrap_or(line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let file = if code.contains("fn main") {
+        code
+    } else {
+        format!("fn main() {{\n{}\n}}", code)
+    };
+    match syn::parse_file(&file) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(syn::Error::new(
+            span,
+            format!("example is not valid Rust: {}", err),
+        )),
+    }
+}
+
+fn unknown_field(key: &Ident) -> syn::Error {
+    let name = key.to_string();
+    let expected = FIELDS
+        .iter()
+        .map(|field| format!("`{}`", field))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = match suggest_field(&name) {
+        Some(field) => format!(
+            "unknown field `{}`, did you mean `{}`?\nexpected one of: {}",
+            name, field, expected
+        ),
+        None => format!("unknown field `{}`\nexpected one of: {}", name, expected),
+    };
+    syn::Error::new(key.span(), message)
+}
+
+fn lint_summary(summary: &LitStr, warnings: &mut Vec<Warning>) {
+    let Some(max_len) =
+        config("CUTEDOGS_SUMMARY_MAX_LEN").and_then(|max_len| max_len.parse::<usize>().ok())
+    else {
+        return;
+    };
+    let text = summary.value();
+    let mut in_code = false;
+    let mut sentences = 1;
+    let mut chars = text.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => in_code = !in_code,
+            '.' | '!' | '?' if !in_code && chars.peek() == Some(&' ') => {
+                chars.next();
+                if chars.peek().is_some_and(|next| next.is_uppercase()) {
+                    sentences += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let length = text.chars().count();
+    let message = if text.trim().contains('\n') {
+        "`summary` spans multiple lines; keep it to a single line".to_string()
+    } else if sentences > 1 {
+        "`summary` has more than one sentence; move the details to another section".to_string()
+    } else if length > max_len {
+        format!(
+            "`summary` is {} characters long, more than the configured {}",
+            length, max_len
+        )
+    } else {
+        return;
+    };
+    warnings.push(Warning {
+        span: summary.span(),
+        message,
+    });
+}
+
+const VALUE_MACROS: &[&str] = &["concat", "env", "include_str"];
+
+thread_local! {
+    static INCLUDE_DIR: std::cell::RefCell<Option<std::path::PathBuf>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct Dependencies {
+    files: Vec<String>,
+    env: Vec<String>,
+}
+
+fn expand_value_macros(tokens: TokenStream2, deps: &mut Dependencies) -> Result<TokenStream2> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut expanded = TokenStream2::new();
+    let mut index = 0;
+    while let Some(token) = tokens.get(index) {
+        match (token, tokens.get(index + 1), tokens.get(index + 2)) {
+            (
+                TokenTree::Ident(name),
+                Some(TokenTree::Punct(bang)),
+                Some(TokenTree::Group(group)),
+            ) if bang.as_char() == '!' && VALUE_MACROS.iter().any(|known| name == known) => {
+                let value = eval_value_macro(name, group.stream(), deps)?;
+                let mut lit = proc_macro2::Literal::string(&value);
+                lit.set_span(name.span());
+                expanded.extend([TokenTree::Literal(lit)]);
+                index += 3;
+                continue;
+            }
+            (TokenTree::Group(group), _, _) => {
+                let stream = expand_value_macros(group.stream(), deps)?;
+                let mut replaced = proc_macro2::Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                expanded.extend([TokenTree::Group(replaced)]);
+            }
+            _ => expanded.extend([token.clone()]),
+        }
+        index += 1;
+    }
+    Ok(expanded)
+}
+
+fn eval_value_macro(name: &Ident, tokens: TokenStream2, deps: &mut Dependencies) -> Result<String> {
+    let tokens = expand_value_macros(tokens, deps)?;
+    let args = syn::parse::Parser::parse2(Punctuated::<Lit, Token![,]>::parse_terminated, tokens)
+        .map_err(|err| {
+        syn::Error::new(
+            err.span(),
+            format!("`{}!` only accepts literals here: {}", name, err),
+        )
+    })?;
+    let first_str = || match args.first() {
+        Some(Lit::Str(lit)) => Ok(lit),
+        _ => Err(syn::Error::new(
+            name.span(),
+            format!("`{}!` expects a string literal", name),
+        )),
+    };
+    match name.to_string().as_str() {
+        "concat" => args
+            .iter()
+            .map(|lit| match lit {
+                Lit::Str(lit) => Ok(lit.value()),
+                Lit::Char(lit) => Ok(lit.value().to_string()),
+                Lit::Int(lit) => Ok(lit.base10_digits().to_string()),
+                Lit::Float(lit) => Ok(lit.base10_digits().to_string()),
+                Lit::Bool(lit) => Ok(lit.value.to_string()),
+                other => Err(syn::Error::new(
+                    other.span(),
+                    "cannot concatenate this literal",
+                )),
+            })
+            .collect(),
+        "env" => {
+            let var = first_str()?;
+            deps.env.push(var.value());
+            std::env::var(var.value()).map_err(|_| {
+                let message = match args.iter().nth(1) {
+                    Some(Lit::Str(message)) => message.value(),
+                    _ => format!(
+                        "environment variable `{}` not defined at compile time",
+                        var.value()
+                    ),
+                };
+                syn::Error::new(var.span(), message)
+            })
+        }
+        _ => {
+            let path = first_str()?;
+            let base = INCLUDE_DIR
+                .with(|dir| dir.borrow().clone())
+                .or_else(|| {
+                    proc_macro::Span::call_site()
+                        .local_file()
+                        .and_then(|file| file.parent().map(std::path::Path::to_path_buf))
+                })
+                .unwrap_or_else(|| {
+                    std::env::var("CARGO_MANIFEST_DIR")
+                        .unwrap_or_default()
+                        .into()
+                });
+            let full_path = std::path::absolute(base.join(path.value()))
+                .map_err(|err| syn::Error::new(path.span(), err.to_string()))?;
+            let text = std::fs::read_to_string(&full_path).map_err(|err| {
+                syn::Error::new(
+                    path.span(),
+                    format!("couldn't read `{}`: {}", full_path.display(), err),
+                )
+            })?;
+            deps.files.push(full_path.to_string_lossy().into_owned());
+            Ok(text.trim_end().to_string())
+        }
+    }
+}
+
+fn string_literals(token: proc_macro2::TokenTree, literals: &mut Vec<LitStr>) {
+    match token {
+        proc_macro2::TokenTree::Group(group) => {
+            for token in group.stream() {
+                string_literals(token, literals);
+            }
+        }
+        proc_macro2::TokenTree::Literal(lit) => {
+            if let Ok(lit) = syn::parse2::<LitStr>(lit.into_token_stream()) {
+                literals.push(lit);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn lint_markdown(lit: &LitStr, warnings: &mut Vec<Warning>) {
+    let text = lit.value();
+    let fences = text.matches("```").count();
+    let prose = text.replace("```", "");
+    let backticks = prose.matches('`').count();
+    let problem = if fences % 2 == 1 {
+        "an unclosed code fence"
+    } else if fences > 0 && !text.contains('\n') {
+        "a code fence in a single-line value"
+    } else if backticks % 2 == 1 {
+        "an unbalanced backtick"
+    } else if has_broken_link(&prose) {
+        "an unclosed intra-doc link"
+    } else {
+        return;
+    };
+    warnings.push(Warning {
+        span: lit.span(),
+        message: format!(
+            "this value contains {}, which breaks the markdown after it",
+            problem
+        ),
+    });
+}
+
+fn has_broken_link(prose: &str) -> bool {
+    let chars: Vec<char> = prose.chars().collect();
+    let mut in_code = false;
+    let mut open = Vec::new();
+    for (i, c) in chars.iter().enumerate() {
+        let next = chars.get(i + 1);
+        match c {
+            '`' => in_code = !in_code,
+            '[' if !in_code => open.push(next == Some(&'`')),
+            ']' if !in_code && open.pop().is_none() && next == Some(&'(') => return true,
+            _ => {}
+        }
+    }
+    open.contains(&true)
+}
+
+fn suggest_field(name: &str) -> Option<&'static str> {
+    FIELDS
+        .iter()
+        .map(|field| (edit_distance(name, field), *field))
+        .filter(|(distance, field)| *distance <= 2.max(field.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| field)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+fn parse_map(input: ParseStream) -> Result<Vec<Param>> {
+    let content;
+    syn::braced!(content in input);
+    let entries = Punctuated::<Param, Token![,]>::parse_terminated(&content)?;
+    Ok(entries.into_iter().collect())
+}
+
+fn parse_text(input: ParseStream) -> Result<LitStr> {
+    if input.peek(LitStr) {
+        return input.parse();
+    }
+    let (lines, span) = parse_lines(input)?;
+    Ok(join_lines(&lines, span))
+}
+
+fn parse_lines(input: ParseStream) -> Result<(Vec<LitStr>, Span)> {
+    let content;
+    let brackets = syn::bracketed!(content in input);
+    let lines = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok((lines.into_iter().collect(), brackets.span.join()))
+}
+
+fn join_lines(lines: &[LitStr], span: Span) -> LitStr {
+    let text = lines
+        .iter()
+        .map(LitStr::value)
+        .collect::<Vec<_>>()
+        .join("\n");
+    LitStr::new(&text, span)
+}
+
+fn push_lines(doc_parts: &mut Vec<Attribute>, text: &str) {
+    for line in text.split('\n') {
+        doc_parts.push(parse_quote! { #[doc = #line] });
+    }
+}
+
+fn parse_list(input: ParseStream) -> Result<Vec<LitStr>> {
+    if input.peek(LitStr) {
+        return Ok(vec![input.parse()?]);
+    }
+    let content;
+    syn::bracketed!(content in input);
+    let entries = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok(entries.into_iter().collect())
+}
+
+#[proc_macro_attribute]
+pub fn document(args: TokenStream, input: TokenStream) -> TokenStream {
+    let raw_args = TokenStream2::from(args.clone());
+    let mut args = parse_macro_input!(args as DocArgs);
+    let mut item = parse_macro_input!(input as Item);
+
+    if let Some(base) = args.inherited.take() {
+        args.inherit_all(&base);
+        args.deprecated_attr = Some(false);
+        args.must_use = None;
+        args.auto_must_use = Some(false);
+    } else if let Some(path) = args.inherit.as_ref() {
+        let result = if path.value().is_empty() {
+            Err(syn::Error::new(
+                path.span(),
+                "`inherit` without a path can only be used inside a `#[document]` trait impl",
+            ))
+        } else {
+            parse_inherit_path(path).map(|(trait_path, method)| {
+                document_inherited(&raw_args, &trait_path, &method, &item)
+            })
+        };
+        return match result {
+            Ok(tokens) => tokens.into(),
+            Err(err) => err.to_compile_error().into(),
+        };
+    }
+
+    if let Item::Struct(ItemStruct { attrs, .. })
+    | Item::Enum(ItemEnum { attrs, .. })
+    | Item::Union(ItemUnion { attrs, .. }) = &mut item
+    {
+        if derives_document(attrs) {
+            attrs.push(parse_quote! { #[documented(#raw_args)] });
+        }
+    }
+
+    if !matches!(item, Item::Impl(_)) {
+        if let Some(attrs) = item_attrs_mut(&mut item) {
+            if let Err(err) = merge_docs(&mut args, attrs) {
+                return err.to_compile_error().into();
+            }
+        }
+    }
+
+    if let Err(err) = read_deprecated(&mut args, item_attrs(&item)) {
+        return err.to_compile_error().into();
+    }
+
+    if !matches!(item, Item::Fn(_)) {
+        if let Err(err) = check_async(&args, None) {
+            return err.to_compile_error().into();
+        }
+    }
+    if let Err(err) = check_deprecation(&args) {
+        return err.to_compile_error().into();
+    }
+
+    let tracking = track_dependencies(&args);
+    let checks = match &item {
+        Item::Struct(ItemStruct { ident, .. })
+        | Item::Enum(ItemEnum { ident, .. })
+        | Item::Union(ItemUnion { ident, .. })
+        | Item::Trait(ItemTrait { ident, .. })
+        | Item::Static(ItemStatic { ident, .. }) => {
+            let warnings = args.warnings.iter().map(Warning::emit);
+            let see_also = check_see_also(&args, Some(ident));
+            quote! { #(#warnings)* #see_also #tracking }
+        }
+        Item::Const(ItemConst { ident, .. }) | Item::Type(ItemType { ident, .. }) => {
+            let warnings = args.warnings.iter().map(Warning::emit);
+            let see_also = check_see_also(&args, Some(ident));
+            assoc_checks(ident, quote! { #(#warnings)* #see_also #tracking })
+        }
+        Item::Use(_) => {
+            let warnings = args.warnings.iter().map(Warning::emit);
+            let see_also = check_see_also(&args, None);
+            quote! { #(#warnings)* #see_also #tracking }
+        }
+        Item::Impl(_) => {
+            let warnings = args.warnings.iter().map(Warning::emit);
+            quote! { #(#warnings)* #tracking }
+        }
+        Item::Mod(_) => tracking,
+        _ => TokenStream2::new(),
+    };
+    let result = match item {
+        Item::Fn(item_fn) => document_fn(&args, item_fn),
+        item @ (Item::Const(_) | Item::Static(_)) => {
+            let doc_parts = render_docs(&args);
+            Ok(quote! {
+                #(#doc_parts)*
+                #item
+            })
+        }
+        Item::Struct(item_struct) => document_struct(&args, item_struct),
+        Item::Enum(item_enum) => document_enum(&args, item_enum),
+        Item::Trait(item_trait) => document_trait(&args, item_trait),
+        Item::Impl(item_impl) => document_impl(&args, item_impl),
+        Item::Mod(item_mod) => document_mod(&args, item_mod),
+        Item::Type(item_type) => document_type(&args, item_type),
+        Item::Union(item_union) => document_union(&args, item_union),
+        Item::Use(item_use) => document_use(&args, item_use),
+        Item::Verbatim(tokens) => match syn::parse2::<ForeignItem>(tokens) {
+            Ok(item @ (ForeignItem::Fn(_) | ForeignItem::Static(_))) => {
+                let doc_parts = match &item {
+                    ForeignItem::Fn(item_fn) => {
+                        render_fn_docs(&args, &item_fn.sig, &item_fn.attrs)
+                    }
+                    _ => render_docs(&args),
+                };
+                Ok(quote! {
+                    #(#doc_parts)*
+                    #item
+                })
+            }
+            Ok(other) => Err(syn::Error::new_spanned(
+                other,
+                "#[document] can only be applied to foreign functions and statics",
+            )),
+            Err(err) => Err(err),
+        },
+        other => Err(syn::Error::new_spanned(
+            other,
+            "#[document] can only be applied to functions, structs, enums, unions, traits, impl blocks, modules, consts, statics and type aliases",
+        )),
+    };
+
+    let guard = platform_guard(&args);
+    match result {
+        Ok(tokens) => quote! {
+            #tokens
+            #guard
+            #checks
+        }
+        .into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+const TARGET_OSES: &[&str] = &[
+    "linux",
+    "ios",
+    "android",
+    "freebsd",
+    "netbsd",
+    "openbsd",
+    "dragonfly",
+    "solaris",
+    "illumos",
+    "fuchsia",
+    "redox",
+    "haiku",
+    "wasi",
+    "emscripten",
+];
+
+fn platform_predicates(platform: &LitStr) -> Option<Vec<TokenStream2>> {
+    platform
+        .value()
+        .split(',')
+        .map(
+            |name| match name.trim().to_lowercase().replace(' ', "").as_str() {
+                "unix" => Some(quote! { unix }),
+                "windows" => Some(quote! { windows }),
+                "macos" | "osx" => Some(quote! { target_os = "macos" }),
+                os if TARGET_OSES.contains(&os) => Some(quote! { target_os = #os }),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+fn doc_cfg(args: &DocArgs) -> Option<Attribute> {
+    let mut predicates = vec![];
+    if let Some(feature) = args.feature.as_ref() {
+        predicates.push(quote! { feature = #feature });
+    }
+    if let Some(platform) = args.platform.as_ref().and_then(platform_predicates) {
+        predicates.push(quote! { any(#(#platform),*) });
+    }
+    if let Some(cfg) = args.cfg.as_ref().and_then(|cfg| cfg.parse::<Meta>().ok()) {
+        predicates.push(cfg.into_token_stream());
+    }
+
+    match predicates.as_slice() {
+        [] => None,
+        [predicate] => Some(parse_quote! { #[cfg_attr(docsrs, doc(cfg(#predicate)))] }),
+        _ => Some(parse_quote! { #[cfg_attr(docsrs, doc(cfg(all(#(#predicates),*))))] }),
+    }
+}
+
+fn platform_guard(args: &DocArgs) -> TokenStream2 {
+    let Some(platform) = args.platform.as_ref() else {
+        return TokenStream2::new();
+    };
+    let Some(predicates) = platform_predicates(platform) else {
+        return TokenStream2::new();
+    };
+
+    let msg = format!(
+        "this item is documented as supporting only {}, but it is being compiled for a different target; update `platform` or the item's `#[cfg]`",
+        platform.value()
+    );
+    quote_spanned! {platform.span()=>
+        #[cfg(not(any(#(#predicates,)* doc, docsrs)))]
+        ::core::compile_error!(#msg);
+    }
+}
+
+#[proc_macro_derive(Document, attributes(documented))]
+pub fn derive_document(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_documented(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn derive_documented(input: DeriveInput) -> Result<TokenStream2> {
+    let mut args = DocArgs::default();
+    let mut lines: Vec<Expr> = vec![];
+    for attr in &input.attrs {
+        if attr.path().is_ident("documented") {
+            args = attr.parse_args()?;
+        } else if let Meta::NameValue(MetaNameValue { path, value, .. }) = &attr.meta {
+            if !path.is_ident("doc") {
+                continue;
+            }
+            match value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => {
+                    let line = doc.value();
+                    let line = line.strip_prefix(' ').unwrap_or(&line);
+                    lines.push(parse_quote! { #line });
+                }
+                value => lines.push(value.clone()),
+            }
+        }
+    }
+
+    let doc = lines.iter().enumerate().map(|(index, line)| match index {
+        0 => quote! { #line },
+        _ => quote! { "\n", #line },
+    });
+    let summary = match args.summary.as_ref() {
+        Some(summary) => Some(interpolate_text(summary, None, Span::call_site())),
+        None => lines
+            .iter()
+            .find(|line| match line {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }) => !line.value().trim().is_empty(),
+                _ => true,
+            })
+            .map(|line| match line {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }) => {
+                    let line = line.value();
+                    let line = line.trim();
+                    parse_quote! { #line }
+                }
+                line => line.clone(),
+            }),
+    };
+    let summary = match summary {
+        Some(summary) => quote! { ::core::option::Option::Some(#summary) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let since = quote_option(&args.since);
+
+    let (names, descs): (Vec<_>, Vec<_>) = args
+        .params
+        .iter()
+        .chain(&args.fields)
+        .chain(&args.variants)
+        .map(|param| (param.name.value(), param.desc.value()))
+        .unzip();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl #impl_generics ::cutedogs::Documented for #ident #ty_generics #where_clause {
+            const DOC: &'static str = ::core::concat!(#(#doc),*);
+
+            fn summary() -> ::core::option::Option<&'static str> {
+                #summary
+            }
+
+            fn params() -> &'static [(&'static str, &'static str)] {
+                &[#((#names, #descs)),*]
+            }
+
+            fn since() -> ::core::option::Option<&'static str> {
+                #since
+            }
+        }
+    })
+}
+
+fn derives_document(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| {
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Document")
+        })
+}
+
+fn quote_option(value: &Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { ::core::option::Option::Some(#value) },
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+#[proc_macro]
+pub fn unimplemented_index(input: TokenStream) -> TokenStream {
+    let name = if input.is_empty() {
+        format_ident!("unimplemented_index")
+    } else {
+        parse_macro_input!(input as Ident)
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let src = std::path::Path::new(&manifest_dir).join("src");
+    let mut index = Index::default();
+    for root in ["lib.rs", "main.rs"] {
+        if let Ok(file) = std::fs::read_to_string(src.join(root)) {
+            if let Ok(file) = syn::parse_file(&file) {
+                if let Err(err) = index_items(&file.items, &[], &src, &src, &mut index) {
+                    return err.to_compile_error().into();
+                }
+            }
+            break;
+        }
+    }
+
+    let mut doc = vec![
+        "Functions that are not implemented yet.".to_string(),
+        String::new(),
+    ];
+    if index.unimplemented.is_empty() {
+        doc.push("Every function is implemented.".to_string());
+    } else {
+        doc.push("| Function | Reason | Tracking issue |".to_string());
+        doc.push("|----------|--------|----------------|".to_string());
+        for (path, reason, tracking_issue) in &index.unimplemented {
+            doc.push(format!(
+                "| `{}` | {} | {} |",
+                path,
+                reason.as_deref().unwrap_or("").replace('|', "\\|"),
+                tracking_issue
+                    .as_deref()
+                    .map(issue_link)
+                    .unwrap_or_default()
+            ));
+        }
+    }
+    if !index.todo.is_empty() {
+        doc.push(String::new());
+        doc.push("# TODO".to_string());
+        for (path, todo) in &index.todo {
+            doc.push(format!("* `{}` - {}", path, todo));
+        }
+    }
+
+    let unimplemented = index
+        .unimplemented
+        .iter()
+        .map(|(path, reason, tracking_issue)| {
+            let reason = quote_option(reason);
+            let tracking_issue = quote_option(tracking_issue);
+            quote! {
+                ::cutedogs::IndexEntry {
+                    path: #path,
+                    reason: #reason,
+                    tracking_issue: #tracking_issue,
+                }
+            }
+        });
+    let todo = index.todo.iter().map(|(path, todo)| {
+        quote! {
+            ::cutedogs::IndexEntry {
+                path: #path,
+                reason: ::core::option::Option::Some(#todo),
+                tracking_issue: ::core::option::Option::None,
+            }
+        }
+    });
+
+    let warning = match index.unimplemented.len() {
+        0 => TokenStream2::new(),
+        1 => emit_warning(
+            proc_macro2::Span::call_site(),
+            "cutedogs: 1 function still marked unimplemented",
+        ),
+        count => emit_warning(
+            proc_macro2::Span::call_site(),
+            &format!("cutedogs: {} functions still marked unimplemented", count),
+        ),
+    };
+
+    let config = track_config();
+    quote! {
+        #warning
+        #config
+
+        #(#[doc = #doc])*
+        pub mod #name {
+            /// Every function marked `unimplemented`.
+            pub const UNIMPLEMENTED: &[::cutedogs::IndexEntry] = &[#(#unimplemented),*];
+
+            /// Every item with a `todo` note or a `todo_body`.
+            pub const TODO: &[::cutedogs::IndexEntry] = &[#(#todo),*];
+        }
+    }
+    .into()
+}
+
+#[derive(Clone)]
+struct Warning {
+    span: proc_macro2::Span,
+    message: String,
+}
+
+impl Warning {
+    fn emit(&self) -> TokenStream2 {
+        emit_warning(self.span, &self.message)
+    }
+}
+
+#[cfg(feature = "nightly")]
+fn emit_warning(span: proc_macro2::Span, message: &str) -> TokenStream2 {
+    span.unwrap().warning(message).emit();
+    TokenStream2::new()
+}
+
+#[cfg(not(feature = "nightly"))]
+fn emit_warning(span: proc_macro2::Span, message: &str) -> TokenStream2 {
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const cutedogs: () = ();
+            cutedogs
+        };
+    }
+}
+
+#[derive(Default)]
+struct Index {
+    unimplemented: Vec<(String, Option<String>, Option<String>)>,
+    todo: Vec<(String, String)>,
+}
+
+fn index_items(
+    items: &[Item],
+    module: &[String],
+    dir: &std::path::Path,
+    file_dir: &std::path::Path,
+    index: &mut Index,
+) -> Result<()> {
+    for item in items {
+        match item {
+            Item::Mod(item_mod) => {
+                let name = item_mod.ident.to_string();
+                let mut module = module.to_vec();
+                module.push(name.clone());
+                let dir = dir.join(&name);
+                if let Some((_, items)) = item_mod.content.as_ref() {
+                    index_items(items, &module, &dir, file_dir, index)?;
+                    continue;
+                }
+                let file = [dir.with_extension("rs"), dir.join("mod.rs")]
+                    .into_iter()
+                    .find_map(|path| Some((std::fs::read_to_string(&path).ok()?, path)))
+                    .and_then(|(file, path)| Some((syn::parse_file(&file).ok()?, path)));
+                if let Some((file, path)) = file {
+                    let file_dir = path.parent().unwrap_or(&dir);
+                    index_items(&file.items, &module, &dir, file_dir, index)?;
+                }
+            }
+            Item::Impl(item_impl) => {
+                let Type::Path(self_ty) = &*item_impl.self_ty else {
+                    continue;
+                };
+                let Some(segment) = self_ty.path.segments.last() else {
+                    continue;
+                };
+                for item in &item_impl.items {
+                    if let ImplItem::Fn(method) = item {
+                        let path = [&segment.ident, &method.sig.ident];
+                        index_item(&method.attrs, module, &path, file_dir, index)?;
+                    }
+                }
+            }
+            Item::Trait(item_trait) => {
+                index_item(
+                    &item_trait.attrs,
+                    module,
+                    &[&item_trait.ident],
+                    file_dir,
+                    index,
+                )?;
+                for item in &item_trait.items {
+                    if let TraitItem::Fn(method) = item {
+                        let path = [&item_trait.ident, &method.sig.ident];
+                        index_item(&method.attrs, module, &path, file_dir, index)?;
+                    }
+                }
+            }
+            Item::Fn(item_fn) => index_item(
+                &item_fn.attrs,
+                module,
+                &[&item_fn.sig.ident],
+                file_dir,
+                index,
+            )?,
+            Item::Struct(ItemStruct { attrs, ident, .. })
+            | Item::Enum(ItemEnum { attrs, ident, .. })
+            | Item::Union(ItemUnion { attrs, ident, .. })
+            | Item::Type(ItemType { attrs, ident, .. })
+            | Item::Const(ItemConst { attrs, ident, .. })
+            | Item::Static(ItemStatic { attrs, ident, .. }) => {
+                index_item(attrs, module, &[ident], file_dir, index)?
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn index_item(
+    attrs: &[Attribute],
+    module: &[String],
+    name: &[&Ident],
+    file_dir: &std::path::Path,
+    index: &mut Index,
+) -> Result<()> {
+    let path = module
+        .iter()
+        .cloned()
+        .chain(name.iter().map(|ident| ident.to_string()))
+        .collect::<Vec<_>>()
+        .join("::");
+    INCLUDE_DIR.with(|dir| *dir.borrow_mut() = Some(file_dir.to_path_buf()));
+    let args = find_document_attr(attrs);
+    INCLUDE_DIR.with(|dir| *dir.borrow_mut() = None);
+    let Some((_, args)) = args.map_err(|err| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("cutedogs: couldn't index `{}`: {}", path, err),
+        )
+    })?
+    else {
+        return Ok(());
+    };
+    if args.is_unimplemented {
+        index.unimplemented.push((
+            path.clone(),
+            args.unimplemented_reason.clone(),
+            args.tracking_issue.clone(),
+        ));
+    }
+    match args.todo_body.as_deref() {
+        Some("") => index
+            .todo
+            .push((path.clone(), "Work in progress".to_string())),
+        Some(reason) => index.todo.push((path.clone(), reason.to_string())),
+        None => {}
+    }
+    if let Some(todo) = args.todo.as_ref() {
+        index.todo.push((path, todo.clone()));
+    }
+    Ok(())
+}
+
+fn document_fn(args: &DocArgs, mut item_fn: ItemFn) -> Result<TokenStream2> {
+    check_generics(args, &item_fn.sig.generics, &item_fn.sig.ident)?;
+    check_async(args, Some(&item_fn.sig))?;
+    let mut checks = check_params(args, &item_fn.sig)?;
+    checks.extend(check_safety(args, &item_fn.sig)?);
+    checks.extend(check_returns(args, &item_fn.sig)?);
+    if matches!(item_fn.vis, Visibility::Public(_)) {
+        checks.extend(check_errors(args, &item_fn.sig));
+    }
+    checks.push(check_see_also(args, Some(&item_fn.sig.ident)));
+    checks.extend(check_panics(args, &item_fn.sig.ident, &item_fn.block));
+    checks.extend(args.warnings.iter().map(Warning::emit));
+    checks.push(track_dependencies(args));
+    stub_body(args, &item_fn.sig, &mut item_fn.attrs, &mut item_fn.block);
+    prepend_checks(&mut item_fn.block, checks);
+
+    let doc_parts = render_fn_docs(args, &item_fn.sig, &item_fn.attrs);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_fn
+    })
+}
+
+fn document_struct(args: &DocArgs, mut item_struct: ItemStruct) -> Result<TokenStream2> {
+    check_generics(args, &item_struct.generics, &item_struct.ident)?;
+    let mut args = args.clone();
+    args.typed_fields = document_fields(&args.fields, &mut item_struct.fields, &item_struct.ident)?;
+    args.item_generics = Some(item_struct.generics.clone());
+
+    let doc_parts = render_docs(&args);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_struct
+    })
+}
+
+fn document_union(args: &DocArgs, mut item_union: ItemUnion) -> Result<TokenStream2> {
+    check_generics(args, &item_union.generics, &item_union.ident)?;
+    let mut args = args.clone();
+    args.typed_fields = document_fields(
+        &args.fields,
+        &mut item_union.fields.named,
+        &item_union.ident,
+    )?;
+    args.item_generics = Some(item_union.generics.clone());
+
+    let safety = args.safety.as_deref().unwrap_or(
+        "Callers must ensure the field being read is the one that was most recently written.",
+    );
+    args.safety = Some(format!(
+        "⚠️ **Reading a field of this union is `unsafe`.** {}",
+        safety
+    ));
+
+    let doc_parts = render_docs(&args);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_union
+    })
+}
+
+fn document_use(args: &DocArgs, item_use: ItemUse) -> Result<TokenStream2> {
+    let prefix = if item_use.leading_colon.is_some() {
+        "::".to_string()
+    } else {
+        String::new()
+    };
+    let mut targets = vec![];
+    use_targets(&item_use.tree, prefix, &mut targets);
+
+    let mut args = args.clone();
+    if let Some(see_also) = args.see_also.as_ref() {
+        targets.push(see_also.clone());
+    }
+    args.see_also = Some(targets.join(", "));
+
+    let doc_parts = render_docs(&args);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_use
+    })
+}
+
+fn use_targets(tree: &UseTree, prefix: String, targets: &mut Vec<String>) {
+    match tree {
+        UseTree::Path(path) => {
+            use_targets(&path.tree, format!("{}{}::", prefix, path.ident), targets)
+        }
+        UseTree::Name(UseName { ident }) | UseTree::Rename(UseRename { ident, .. }) => {
+            if ident == "self" {
+                targets.push(prefix.trim_end_matches("::").to_string());
+            } else {
+                targets.push(format!("{}{}", prefix, ident));
+            }
+        }
+        UseTree::Glob(_) => targets.push(prefix.trim_end_matches("::").to_string()),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                use_targets(tree, prefix.clone(), targets);
+            }
+        }
+    }
+}
+
+fn document_fields<'a>(
+    documented: &[Param],
+    fields: impl IntoIterator<Item = &'a mut Field>,
+    item: &Ident,
+) -> Result<Vec<(String, String)>> {
+    let mut fields: Vec<&mut Field> = fields.into_iter().collect();
+    let mut typed_fields = vec![];
+    for param in documented {
+        let key = param.name.value();
+        let field = fields
+            .iter_mut()
+            .enumerate()
+            .find(|(index, field)| match &field.ident {
+                Some(ident) => *ident == key,
+                None => index.to_string() == key,
+            });
+
+        let Some((_, field)) = field else {
+            return Err(syn::Error::new(
+                param.name.span(),
+                format!("no field named `{}` on `{}`", key, item),
+            ));
+        };
+        let desc = &param.desc;
+        field.attrs.push(parse_quote! { #[doc = #desc] });
+        typed_fields.push((
+            format!("{}: {}", key, type_to_string(&field.ty)),
+            desc.value(),
+        ));
+    }
+    Ok(typed_fields)
+}
+
+fn type_to_string(ty: &Type) -> String {
+    let mut rendered = ty.to_token_stream().to_string();
+    for (from, to) in [
+        (" < ", "<"),
+        ("< ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        ("* const ", "*const "),
+        ("* mut ", "*mut "),
+    ] {
+        rendered = rendered.replace(from, to);
+    }
+    rendered
+}
+
+fn document_enum(args: &DocArgs, mut item_enum: ItemEnum) -> Result<TokenStream2> {
+    check_generics(args, &item_enum.generics, &item_enum.ident)?;
+    for param in &args.variants {
+        let key = param.name.value();
+        let Some(variant) = item_enum
+            .variants
+            .iter_mut()
+            .find(|variant| variant.ident == key)
+        else {
+            return Err(syn::Error::new(
+                param.name.span(),
+                format!("no variant named `{}` on enum `{}`", key, item_enum.ident),
+            ));
+        };
+        let desc = &param.desc;
+        variant.attrs.push(parse_quote! { #[doc = #desc] });
+    }
+
+    let doc_parts = render_generic_docs(args, &item_enum.generics);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_enum
+    })
+}
+
+fn document_trait(args: &DocArgs, mut item_trait: ItemTrait) -> Result<TokenStream2> {
+    check_generics(args, &item_trait.generics, &item_trait.ident)?;
+    let mut tracking = Vec::new();
+    let mut required_checks = Vec::new();
+    for MethodDocs { name, args, .. } in &args.methods {
+        let Some(method) = item_trait.items.iter_mut().find_map(|item| match item {
+            TraitItem::Fn(method) if method.sig.ident == *name => Some(method),
+            _ => None,
+        }) else {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("no method named `{}` on trait `{}`", name, item_trait.ident),
+            ));
+        };
+        check_generics(args, &method.sig.generics, name)?;
+        check_async(args, Some(&method.sig))?;
+        check_deprecation(args)?;
+        let mut checks = check_params(args, &method.sig)?;
+        checks.extend(check_safety(args, &method.sig)?);
+        checks.extend(check_returns(args, &method.sig)?);
+        checks.push(check_see_also(args, Some(name)));
+        checks.extend(args.warnings.iter().map(Warning::emit));
+        if let Some(block) = method.default.as_mut() {
+            checks.extend(check_panics(args, name, block));
+            stub_body(args, &method.sig, &mut method.attrs, block);
+            prepend_checks(block, checks);
+        } else {
+            required_checks.extend(checks);
+        }
+        let doc_parts = render_fn_docs(args, &method.sig, &method.attrs);
+        method.attrs.splice(0..0, doc_parts);
+        tracking.push(track_dependencies(args));
+    }
+
+    for param in &args.assoc {
+        let key = param.name.value();
+        let Some(attrs) = item_trait.items.iter_mut().find_map(|item| match item {
+            TraitItem::Type(ty) if ty.ident == key => Some(&mut ty.attrs),
+            TraitItem::Const(konst) if konst.ident == key => Some(&mut konst.attrs),
+            _ => None,
+        }) else {
+            return Err(syn::Error::new(
+                param.name.span(),
+                format!(
+                    "no associated type or const named `{}` on trait `{}`",
+                    key, item_trait.ident
+                ),
+            ));
+        };
+        let desc = &param.desc;
+        attrs.push(parse_quote! { #[doc = #desc] });
+    }
+
+    let mut registered: Vec<(Ident, TokenStream2)> = args
+        .methods
+        .iter()
+        .map(|method| (method.name.clone(), method.tokens.clone()))
+        .collect();
+    for item in &mut item_trait.items {
+        let TraitItem::Fn(method) = item else {
+            continue;
+        };
+        let Some((index, mut method_args)) = find_document_attr(&method.attrs)? else {
+            continue;
+        };
+        check_generics(&method_args, &method.sig.generics, &method.sig.ident)?;
+        check_async(&method_args, Some(&method.sig))?;
+        check_deprecation(&method_args)?;
+        let mut checks = check_params(&method_args, &method.sig)?;
+        checks.extend(check_safety(&method_args, &method.sig)?);
+        checks.extend(check_returns(&method_args, &method.sig)?);
+        checks.push(check_see_also(&method_args, Some(&method.sig.ident)));
+        checks.extend(method_args.warnings.iter().map(Warning::emit));
+        if let Some(block) = method.default.as_mut() {
+            checks.extend(check_panics(&method_args, &method.sig.ident, block));
+            stub_body(&method_args, &method.sig, &mut method.attrs, block);
+            prepend_checks(block, checks);
+        } else {
+            required_checks.extend(checks);
+        }
+        let tokens = attr_tokens(&method.attrs.remove(index));
+        merge_docs(&mut method_args, &mut method.attrs)?;
+        let index = index.min(method.attrs.len());
+        let doc_parts = render_fn_docs(&method_args, &method.sig, &method.attrs);
+        method.attrs.splice(index..index, doc_parts);
+        registered.push((method.sig.ident.clone(), tokens));
+        tracking.push(track_dependencies(&method_args));
+    }
+
+    let trait_ident = &item_trait.ident;
+    let registry = format_ident!("__cutedogs_{}", trait_ident);
+    let (names, tokens): (Vec<_>, Vec<_>) = registered.into_iter().unzip();
+    let unknown = format!("no documentation registered for `{}::", item_trait.ident);
+
+    let doc_parts = render_generic_docs(args, &item_trait.generics);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_trait
+        #(#tracking)*
+        const _: () = { #(#required_checks)* };
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #registry {
+            #(
+                (#names, { $($overrides:tt)* }, $($item:tt)*) => {
+                    #[::cutedogs::document(__inherited = { #tokens }, $($overrides)*)]
+                    $($item)*
+                };
+            )*
+            ($method:ident, $($rest:tt)*) => {
+                ::core::compile_error!(::core::concat!(#unknown, ::core::stringify!($method), "`"));
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #registry as #trait_ident;
+    })
+}
+
+fn registry_path(trait_path: &Path) -> Path {
+    let mut path = trait_path.clone();
+    for segment in &mut path.segments {
+        segment.arguments = PathArguments::None;
+    }
+    path
+}
+
+fn parse_inherit_path(path: &LitStr) -> Result<(Path, Ident)> {
+    let mut parsed: Path = path.parse()?;
+    let (Some(method), false) = (parsed.segments.pop(), parsed.segments.is_empty()) else {
+        return Err(syn::Error::new(
+            path.span(),
+            "expected a path of the form `Trait::method`",
+        ));
+    };
+    parsed.segments.pop_punct();
+    Ok((registry_path(&parsed), method.into_value().ident))
+}
+
+fn document_inherited(
+    raw_args: &TokenStream2,
+    trait_path: &Path,
+    method: &Ident,
+    item: &impl ToTokens,
+) -> TokenStream2 {
+    quote! {
+        #trait_path! { #method, { #raw_args }, #item }
+    }
+}
+
+fn document_impl(args: &DocArgs, mut item_impl: ItemImpl) -> Result<TokenStream2> {
+    let trait_path = item_impl
+        .trait_
+        .as_ref()
+        .map(|(_, path, _)| registry_path(path));
+
+    let mut tracking = Vec::new();
+    for item in &mut item_impl.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let (index, mut method_args) = match find_document_attr(&method.attrs)? {
+            Some((
+                index,
+                DocArgs {
+                    inherit: Some(path),
+                    ..
+                },
+            )) => {
+                let (trait_path, method_ident) = match (path.value().is_empty(), &trait_path) {
+                    (false, _) => parse_inherit_path(&path)?,
+                    (true, Some(trait_path)) => (trait_path.clone(), method.sig.ident.clone()),
+                    (true, None) => {
+                        return Err(syn::Error::new(
+                            path.span(),
+                            "`inherit` without a path can only be used inside a trait impl",
+                        ))
+                    }
+                };
+                let raw_args = attr_tokens(&method.attrs.remove(index));
+                *item = ImplItem::Verbatim(document_inherited(
+                    &raw_args,
+                    &trait_path,
+                    &method_ident,
+                    method,
+                ));
+                continue;
+            }
+            Some((index, method_args)) => {
+                method.attrs.remove(index);
+                (index, method_args)
+            }
+            None => (method.attrs.len(), DocArgs::default()),
+        };
+        method_args.inherit(args);
+        merge_docs(&mut method_args, &mut method.attrs)?;
+        read_deprecated(&mut method_args, &method.attrs)?;
+        if trait_path.is_some() {
+            method_args.deprecated_attr = Some(false);
+            method_args.must_use = None;
+            method_args.auto_must_use = Some(false);
+        }
+        check_generics(&method_args, &method.sig.generics, &method.sig.ident)?;
+        check_async(&method_args, Some(&method.sig))?;
+        check_deprecation(&method_args)?;
+        let mut checks = check_params(&method_args, &method.sig)?;
+        if trait_path.is_none() {
+            checks.extend(check_safety(&method_args, &method.sig)?);
+            if matches!(method.vis, Visibility::Public(_)) {
+                checks.extend(check_errors(&method_args, &method.sig));
+            }
+        }
+        checks.extend(check_returns(&method_args, &method.sig)?);
+        checks.push(check_see_also(&method_args, Some(&method.sig.ident)));
+        checks.extend(check_panics(&method_args, &method.sig.ident, &method.block));
+        checks.extend(method_args.warnings.iter().map(Warning::emit));
+        stub_body(
+            &method_args,
+            &method.sig,
+            &mut method.attrs,
+            &mut method.block,
+        );
+        prepend_checks(&mut method.block, checks);
+        if item_impl.generics.params.is_empty() {
+            method_args.self_ty = Some((*item_impl.self_ty).clone());
+        }
+        let index = index.min(method.attrs.len());
+        let doc_parts = render_fn_docs(&method_args, &method.sig, &method.attrs);
+        method.attrs.splice(index..index, doc_parts);
+        tracking.push(track_dependencies(&method_args));
+    }
+
+    let impl_args = DocArgs {
+        summary: args.summary.clone(),
+        examples: args.examples.clone(),
+        feature: args.feature.clone(),
+        cfg: args.cfg.clone(),
+        ..Default::default()
+    };
+    let doc_parts = render_docs(&impl_args);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_impl
+        #(#tracking)*
+    })
+}
+
+fn document_mod(args: &DocArgs, mut item_mod: ItemMod) -> Result<TokenStream2> {
+    let mut doc_parts = render_docs(args);
+    if let Some((_, items)) = item_mod.content.as_mut() {
+        let checks = args
+            .warnings
+            .iter()
+            .map(Warning::emit)
+            .chain([check_see_also(args, Some(&item_mod.ident))]);
+        for check in checks.filter(|check| !check.is_empty()) {
+            items.push(Item::Verbatim(check));
+        }
+        for attr in &mut doc_parts {
+            attr.style = AttrStyle::Inner(Default::default());
+        }
+        let index = item_mod
+            .attrs
+            .iter()
+            .position(|attr| matches!(attr.style, AttrStyle::Inner(_)))
+            .unwrap_or(item_mod.attrs.len());
+        item_mod.attrs.splice(index..index, doc_parts);
+        return Ok(quote! { #item_mod });
+    }
+
+    let warnings = args.warnings.iter().map(Warning::emit);
+    let see_also = check_see_also(args, Some(&item_mod.ident));
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_mod
+        #(#warnings)*
+        #see_also
+    })
+}
+
+fn document_type(args: &DocArgs, item_type: ItemType) -> Result<TokenStream2> {
+    check_generics(args, &item_type.generics, &item_type.ident)?;
+
+    let doc_parts = render_generic_docs(args, &item_type.generics);
+    Ok(quote! {
+        #(#doc_parts)*
+        #item_type
+    })
+}
+
+fn check_generics(args: &DocArgs, generics: &Generics, item: &Ident) -> Result<()> {
+    for param in &args.generics {
+        let key = param.name.value();
+        let declared = generics.params.iter().any(|generic| match generic {
+            GenericParam::Type(ty) => ty.ident == key,
+            GenericParam::Const(konst) => konst.ident == key,
+            GenericParam::Lifetime(_) => false,
+        });
+        if !declared {
+            return Err(syn::Error::new(
+                param.name.span(),
+                format!("no type parameter named `{}` on `{}`", key, item),
+            ));
+        }
+    }
+    for param in &args.lifetimes {
+        let key = param.name.value();
+        if !generics
+            .lifetimes()
+            .any(|def| def.lifetime.to_string() == key)
+        {
+            return Err(syn::Error::new(
+                param.name.span(),
+                format!("no lifetime named `{}` on `{}`", key, item),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn read_deprecated(args: &mut DocArgs, attrs: &[Attribute]) -> Result<()> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("deprecated")) else {
+        return Ok(());
+    };
+    args.deprecated_attr = Some(false);
+    let (mut note, mut since) = (None, None);
+    match &attr.meta {
+        Meta::Path(_) => args.deprecated_bare = true,
+        Meta::NameValue(meta) => note = Some(syn::parse2::<LitStr>(meta.value.to_token_stream())?),
+        Meta::List(_) => attr.parse_nested_meta(|meta| {
+            let value = meta.value()?.parse::<LitStr>()?;
+            if meta.path.is_ident("note") {
+                note = Some(value);
+            } else if meta.path.is_ident("since") {
+                since = Some(value);
+            }
+            Ok(())
+        })?,
+    }
+    if args.deprecated.is_none() {
+        args.deprecated = note.map(|note| note.value());
+    }
+    if args.deprecated_since.is_none() {
+        args.deprecated_since = since.map(|since| since.value());
+    }
+    Ok(())
+}
+
+fn check_deprecation(args: &DocArgs) -> Result<()> {
+    if !is_strict(args) {
+        return Ok(());
+    }
+    match (args.deprecated.is_some(), args.deprecated_since.is_some()) {
+        (true, false) => Err(syn::Error::new(
+            args.span_of("deprecated"),
+            "`deprecated` requires `deprecated_since` in strict mode",
+        )),
+        (false, true) => Err(syn::Error::new(
+            args.span_of("deprecated_since"),
+            "`deprecated_since` requires `deprecated` in strict mode",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_async(args: &DocArgs, sig: Option<&Signature>) -> Result<()> {
+    match args.cancellation_safety.as_ref() {
+        Some(lit) if sig.is_none_or(|sig| sig.asyncness.is_none()) => Err(syn::Error::new(
+            lit.span(),
+            "`cancellation_safety` can only be used on an `async fn`",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_params(args: &DocArgs, sig: &Signature) -> Result<Vec<TokenStream2>> {
+    let mut inputs = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => inputs.push(Ident::new("self", receiver.self_token.span)),
+            FnArg::Typed(typed) => pat_idents(&typed.pat, &mut inputs),
+        }
+    }
+
+    for unit in &args.param_units {
+        let key = unit.name.value();
+        if key != "returns" && !inputs.iter().any(|input| *input == key) {
+            return Err(syn::Error::new(
+                unit.name.span(),
+                format!("no parameter named `{}` on `{}`", key, sig.ident),
+            ));
+        }
+    }
+
+    if args.params_inherited {
+        return Ok(Vec::new());
+    }
+
+    for param in &args.params {
+        let key = param.name.value();
+        if key != "self" && !inputs.iter().any(|input| *input == key) {
+            return Err(syn::Error::new(
+                param.name.span(),
+                format!("no parameter named `{}` on `{}`", key, sig.ident),
+            ));
+        }
+    }
+
+    let strict = is_strict(args);
+    if args.params.is_empty() && !strict {
+        return Ok(Vec::new());
+    }
+    let mut errors: Option<syn::Error> = None;
+    let mut warnings = Vec::new();
+    for input in &inputs {
+        if input == "self"
+            || input.to_string().starts_with('_')
+            || args.params.iter().any(|param| *input == param.name.value())
+        {
+            continue;
+        }
+        let message = format!("parameter `{}` of `{}` is not documented", input, sig.ident);
+        if strict {
+            let error = syn::Error::new(input.span(), message);
+            match errors.as_mut() {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        } else {
+            warnings.push(emit_warning(input.span(), &message));
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(warnings),
+    }
+}
+
+fn check_safety(args: &DocArgs, sig: &Signature) -> Result<Option<TokenStream2>> {
+    let Some(unsafety) = sig.unsafety.as_ref() else {
+        return Ok(None);
+    };
+    if args.safety.is_some() {
+        return Ok(None);
+    }
+    let message = format!("`unsafe fn {}` must document `safety`", sig.ident);
+    match config("CUTEDOGS_MISSING_SAFETY").as_deref() {
+        Some("warn") => Ok(Some(emit_warning(unsafety.span, &message))),
+        Some("allow") => Ok(None),
+        _ => Err(syn::Error::new(unsafety.span, message)),
+    }
+}
+
+fn check_returns(args: &DocArgs, sig: &Signature) -> Result<Option<TokenStream2>> {
+    let unit = match &sig.output {
+        ReturnType::Default => true,
+        ReturnType::Type(_, ty) => matches!(&**ty, Type::Tuple(tuple) if tuple.elems.is_empty()),
+    };
+    match args.returns.as_ref() {
+        Some(returns) if unit => Ok(Some(emit_warning(
+            returns.span(),
+            &format!("`returns` is documented but `{}` returns `()`", sig.ident),
+        ))),
+        None if !unit && is_strict(args) && unit_of(args, "returns").is_none() => {
+            Err(syn::Error::new_spanned(
+                &sig.output,
+                format!(
+                    "`{}` returns a value but `returns` is not documented",
+                    sig.ident
+                ),
+            ))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn check_errors(args: &DocArgs, sig: &Signature) -> Option<TokenStream2> {
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return None;
+    };
+    let Type::Path(path) = &**ty else {
+        return None;
+    };
+    let result = path.path.segments.last()?;
+    if result.ident != "Result"
+        || args.errors.is_some()
+        || !args.error_variants.is_empty()
+        || args.is_unimplemented
+        || result_error(&sig.output).is_some()
+    {
+        return None;
+    }
+    Some(emit_warning(
+        result.ident.span(),
+        &format!(
+            "`{}` returns a `Result` but does not document `errors`",
+            sig.ident
+        ),
+    ))
+}
+
+const PANIC_MACROS: &[&str] = &[
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "unreachable",
+    "todo",
+    "unimplemented",
+];
+
+fn check_panics(args: &DocArgs, name: &Ident, block: &Block) -> Option<TokenStream2> {
+    if args.panics.is_some()
+        || args.allow_undocumented_panics
+        || args.is_unimplemented
+        || args.todo_body.is_some()
+        || args.stub_default
+    {
+        return None;
+    }
+    let (span, site) = find_panic(block.to_token_stream())?;
+    Some(emit_warning(
+        span,
+        &format!(
+            "`{}` can panic via {} but does not document `panics`; add `panics` or `allow_undocumented_panics`",
+            name, site
+        ),
+    ))
+}
+
+fn find_panic(tokens: TokenStream2) -> Option<(Span, String)> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    for (index, token) in tokens.iter().enumerate() {
+        let after_dot = index > 0
+            && matches!(&tokens[index - 1], TokenTree::Punct(punct) if punct.as_char() == '.');
+        let next = tokens.get(index + 1);
+        match token {
+            TokenTree::Group(group) => {
+                if let Some(found) = find_panic(group.stream()) {
+                    return Some(found);
+                }
+            }
+            TokenTree::Ident(ident)
+                if after_dot
+                    && (ident == "unwrap" || ident == "expect")
+                    && matches!(next, Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis) =>
+            {
+                return Some((ident.span(), format!("`{}()`", ident)));
+            }
+            TokenTree::Ident(ident)
+                if !after_dot
+                    && PANIC_MACROS.iter().any(|name| ident == name)
+                    && matches!(next, Some(TokenTree::Punct(punct)) if punct.as_char() == '!') =>
+            {
+                return Some((ident.span(), format!("`{}!`", ident)));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn returned_type(output: &ReturnType) -> Option<String> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let Type::Path(path) = &**ty else {
+        return None;
+    };
+    path.path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+}
+
+fn result_error(output: &ReturnType) -> Option<&Type> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let Type::Path(path) = &**ty else {
+        return None;
+    };
+    let result = path.path.segments.last()?;
+    let PathArguments::AngleBracketed(generics) = &result.arguments else {
+        return None;
+    };
+    match (
+        result.ident == "Result",
+        generics.args.len(),
+        generics.args.last(),
+    ) {
+        (true, 2, Some(GenericArgument::Type(error))) => Some(error),
+        _ => None,
+    }
+}
+
+fn is_strict(args: &DocArgs) -> bool {
+    args.strict.unwrap_or_else(|| {
+        config("CUTEDOGS_STRICT").is_some_and(|strict| strict == "1" || strict == "true")
+    })
+}
+
+fn pat_idents(pat: &Pat, idents: &mut Vec<Ident>) {
+    match pat {
+        Pat::Ident(pat) => idents.push(pat.ident.clone()),
+        Pat::Reference(pat) => pat_idents(&pat.pat, idents),
+        Pat::Paren(pat) => pat_idents(&pat.pat, idents),
+        Pat::Type(pat) => pat_idents(&pat.pat, idents),
+        Pat::Tuple(pat) => pat.elems.iter().for_each(|elem| pat_idents(elem, idents)),
+        Pat::TupleStruct(pat) => pat.elems.iter().for_each(|elem| pat_idents(elem, idents)),
+        Pat::Slice(pat) => pat.elems.iter().for_each(|elem| pat_idents(elem, idents)),
+        Pat::Struct(pat) => pat
+            .fields
+            .iter()
+            .for_each(|field| pat_idents(&field.pat, idents)),
+        _ => {}
+    }
+}
+
+const CONFIG_VARS: &[&str] = &[
+    "CUTEDOGS_AUTO_MUST_USE",
+    "CUTEDOGS_CANCELLATION_NOTICE",
+    "CUTEDOGS_ISSUE_URL",
+    "CUTEDOGS_MISSING_SAFETY",
+    "CUTEDOGS_MSRV",
+    "CUTEDOGS_STRICT",
+    "CUTEDOGS_SUMMARY_MAX_LEN",
+    "CUTEDOGS_UNKNOWN_FIELDS",
+];
+
+fn config(name: &str) -> Option<String> {
+    debug_assert!(
+        CONFIG_VARS.contains(&name),
+        "untracked config variable `{}`",
+        name
+    );
+    std::env::var(name).ok()
+}
+
+fn track_config() -> TokenStream2 {
+    let vars = CONFIG_VARS;
+    quote! {
+        #(const _: ::core::option::Option<&str> = ::core::option_env!(#vars);)*
+    }
+}
+
+fn track_dependencies(args: &DocArgs) -> TokenStream2 {
+    let files = &args.tracked_files;
+    let env = &args.tracked_env;
+    let config = track_config();
+    quote! {
+        #(const _: &str = ::core::include_str!(#files);)*
+        #(const _: ::core::option::Option<&str> = ::core::option_env!(#env);)*
+        #config
+    }
+}
+
+fn assoc_checks(ident: &Ident, checks: TokenStream2) -> TokenStream2 {
+    if checks.is_empty() {
+        return checks;
+    }
+    let name = format_ident!("__CUTEDOGS_{}", ident);
+    quote! {
+        #[doc(hidden)]
+        #[allow(dead_code, non_upper_case_globals)]
+        const #name: () = { #checks };
+    }
+}
+
+fn prepend_checks(block: &mut Block, checks: Vec<TokenStream2>) {
+    for check in checks.into_iter().filter(|check| !check.is_empty()) {
+        block.stmts.insert(0, Stmt::Item(Item::Verbatim(check)));
+    }
+}
+
+fn check_see_also(args: &DocArgs, name: Option<&Ident>) -> TokenStream2 {
+    let self_reference = name.filter(|name| {
+        args.see_also
+            .iter()
+            .flat_map(|see_also| see_also.split(','))
+            .map(|entry| entry.trim().trim_end_matches("()"))
+            .any(|entry| {
+                **name == entry
+                    || entry
+                        .strip_prefix("Self::")
+                        .is_some_and(|entry| **name == entry)
+            })
+    });
+    let warning = if let Some(name) = self_reference {
+        emit_warning(
+            args.span_of("see_also"),
+            &format!("`see_also` of `{}` refers to `{}` itself", name, name),
+        )
+    } else {
+        TokenStream2::new()
+    };
+    if args.see_also_paths.is_empty() {
+        return warning;
+    }
+    let paths = args.see_also_paths.iter().map(|path| {
+        let mut path = path.clone();
+        let owner = path
+            .segments
+            .iter()
+            .take(path.segments.len() - 1)
+            .position(|segment| segment.ident.to_string().starts_with(char::is_uppercase));
+        if let Some(owner) = owner {
+            path.segments = path.segments.into_iter().take(owner + 1).collect();
+        }
+        path
+    });
+    let uses = paths.map(|path| {
+        let span = path.segments[0].ident.span();
+        quote_spanned! {span=>
+            #[allow(unused_imports)]
+            use #path as _;
+        }
+    });
+    quote! {
+        #warning
+        const _: () = {
+            #(#uses)*
+        };
+    }
+}
+
+fn parse_see_also(input: ParseStream) -> Result<Vec<Path>> {
+    let mut paths = Vec::new();
+    while !input.is_empty() {
+        let path = input.call(Path::parse_mod_style)?;
+        if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+        } else if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            if !content.is_empty() {
+                return Err(content.error("expected `()`"));
+            }
+            paths.push(path);
+        } else {
+            paths.push(path);
+        }
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    Ok(paths)
+}
+
+fn panic_message(prefix: &str, reason: &str) -> LitStr {
+    let escaped = reason.replace('{', "{{").replace('}', "}}");
+    LitStr::new(
+        &format!("{prefix}: {escaped}"),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+fn stub_body(args: &DocArgs, sig: &Signature, attrs: &mut Vec<Attribute>, block: &mut Block) {
+    let panics = args.is_unimplemented || args.todo_body.is_some();
+    *block = match (args.is_unimplemented, args.todo_body.as_deref()) {
+        (true, _) => match args.unimplemented_reason.as_ref() {
+            Some(reason) => {
+                let message = panic_message("not implemented", reason);
+                parse_quote! {{ ::core::panic!(#message) }}
+            }
+            None => parse_quote! {{ ::core::unimplemented!() }},
+        },
+        (false, Some("")) => parse_quote! {{ ::core::todo!() }},
+        (false, Some(reason)) => {
+            let message = panic_message("not yet implemented", reason);
+            parse_quote! {{ ::core::panic!(#message) }}
+        }
+        (false, None) if args.stub_default => {
+            parse_quote! {{ ::core::default::Default::default() }}
+        }
+        (false, None) => return,
+    };
+    attrs.push(parse_quote! { #[allow(unused_variables)] });
+    if panics {
+        attrs.push(parse_quote! { #[cold] });
+        let rust_abi = sig
+            .abi
+            .as_ref()
+            .is_none_or(|abi| abi.name.as_ref().is_some_and(|name| name.value() == "Rust"));
+        if sig.asyncness.is_none() && rust_abi {
+            attrs.push(parse_quote! { #[track_caller] });
+        }
+    }
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Fn(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::Const(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+fn item_attrs_mut(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Fn(item) => Some(&mut item.attrs),
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::Union(item) => Some(&mut item.attrs),
+        Item::Trait(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Mod(item) => Some(&mut item.attrs),
+        Item::Const(item) => Some(&mut item.attrs),
+        Item::Static(item) => Some(&mut item.attrs),
+        Item::Type(item) => Some(&mut item.attrs),
+        Item::Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+fn is_doc_comment(attr: &Attribute) -> bool {
+    matches!(attr.style, AttrStyle::Outer)
+        && matches!(&attr.meta, Meta::NameValue(meta) if meta.path.is_ident("doc"))
+}
+
+fn merge_docs(args: &mut DocArgs, attrs: &mut Vec<Attribute>) -> Result<()> {
+    match args.merge.as_deref() {
+        None => {}
+        Some("error") => {
+            if let Some(attr) = attrs.iter().find(|attr| is_doc_comment(attr)) {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "item already has doc comments, which `merge = \"error\"` forbids",
+                ));
+            }
+        }
+        Some(_) => {
+            let (handwritten, rest) = std::mem::take(attrs).into_iter().partition(is_doc_comment);
+            *attrs = rest;
+            args.handwritten = handwritten;
+        }
+    }
+    Ok(())
+}
+
+fn is_document_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "document")
+}
+
+fn find_document_attr(attrs: &[Attribute]) -> Result<Option<(usize, DocArgs)>> {
+    let Some(index) = attrs.iter().position(is_document_attr) else {
+        return Ok(None);
+    };
+    let args = match &attrs[index].meta {
+        Meta::Path(_) => DocArgs::default(),
+        _ => attrs[index].parse_args()?,
+    };
+    Ok(Some((index, args)))
+}
+
+fn attr_tokens(attr: &Attribute) -> TokenStream2 {
+    match &attr.meta {
+        Meta::List(list) => list.tokens.clone(),
+        _ => TokenStream2::new(),
+    }
+}
+
+fn link_code_spans(text: &str) -> String {
+    let mut linked = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('`') {
+        let Some(len) = rest[start + 1..].find('`') else {
+            break;
+        };
+        let span = &rest[start + 1..start + 1 + len];
+        linked.push_str(&rest[..start]);
+        if is_item_path(span) && !linked.ends_with('[') {
+            linked.push_str(&format!("[`{}`]", span));
+        } else {
+            linked.push_str(&format!("`{}`", span));
+        }
+        rest = &rest[start + len + 2..];
+    }
+    linked.push_str(rest);
+    linked
+}
+
+fn is_item_path(span: &str) -> bool {
+    let path = span.strip_suffix("()").unwrap_or(span);
+    !path.is_empty()
+        && path.split("::").all(|segment| {
+            segment
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+}
+
+fn issue_link(issue: &str) -> String {
+    let issue = issue.trim_start_matches('#');
+    let template = config("CUTEDOGS_ISSUE_URL").or_else(|| {
+        std::env::var("CARGO_PKG_REPOSITORY")
+            .ok()
+            .filter(|repository| !repository.is_empty())
+            .map(|repository| format!("{}/issues/{{}}", repository.trim_end_matches('/')))
+    });
+    match template {
+        Some(template) => format!("[#{}]({})", issue, template.replace("{}", issue)),
+        None => format!("#{}", issue),
+    }
+}
+
+fn reference_link(reference: &str) -> String {
+    if reference.starts_with("https://") || reference.starts_with("http://") {
+        return format!("<{}>", reference);
+    }
+    let rfc = reference
+        .strip_prefix("RFC ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .filter(|number| number.chars().all(|c| c.is_ascii_digit()));
+    match rfc {
+        Some(number) => format!(
+            "[{}](https://www.rfc-editor.org/rfc/rfc{})",
+            reference, number
+        ),
+        None => reference.to_string(),
+    }
+}
+
+fn link_citations(text: &str, references: &[String]) -> String {
+    let mut text = text.to_string();
+    for reference in references {
+        let link = reference_link(reference);
+        let link = if link == *reference {
+            format!("[{}](#references)", reference)
+        } else {
+            link
+        };
+        text = text.replacen(reference.as_str(), &link, 1);
+    }
+    text
+}
+
+fn tidy_tokens(tokens: &impl ToTokens) -> String {
+    let mut text = tokens.to_token_stream().to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        (" (", "("),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+        ("* const ", "*const "),
+        ("* mut ", "*mut "),
+        ("? ", "?"),
+    ] {
+        text = text.replace(from, to);
+    }
+    text
+}
+
+fn generic_bounds(generics: &Generics) -> Vec<(String, Option<String>)> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) => {
+                let mut bounds = ty.bounds.iter().map(tidy_tokens).collect::<Vec<_>>();
+                let predicates = generics.where_clause.iter().flat_map(|clause| &clause.predicates);
+                for predicate in predicates {
+                    if let WherePredicate::Type(predicate) = predicate {
+                        if matches!(&predicate.bounded_ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(&ty.ident)) {
+                            bounds.extend(predicate.bounds.iter().map(tidy_tokens));
+                        }
+                    }
+                }
+                let bounds = (!bounds.is_empty()).then(|| bounds.join(" + "));
+                Some((ty.ident.to_string(), bounds))
+            }
+            GenericParam::Const(konst) => Some((konst.ident.to_string(), Some(tidy_tokens(&konst.ty)))),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+fn unit_of(args: &DocArgs, name: &str) -> Option<String> {
+    args.param_units
+        .iter()
+        .find(|param| param.name.value() == name)
+        .map(|param| param.desc.value())
+}
+
+fn render_fn_docs(args: &DocArgs, sig: &Signature, attrs: &[Attribute]) -> Vec<Attribute> {
+    let mut args = args.clone();
+    args.signature = Some(sig.clone());
+    args.item_generics = Some(sig.generics.clone());
+    if args.auto_summary && args.summary.is_none() {
+        args.summary = Some(format!("{} (TODO: review)", summarize_name(&sig.ident)));
+    }
+    let auto_must_use = args.auto_must_use.unwrap_or_else(|| {
+        config("CUTEDOGS_AUTO_MUST_USE").is_some_and(|auto| auto == "1" || auto == "true")
+    });
+    if auto_must_use
+        && args.must_use.is_none()
+        && sig.asyncness.is_none()
+        && !attrs.iter().any(|attr| attr.path().is_ident("must_use"))
+    {
+        args.must_use = match returned_type(&sig.output).as_deref() {
+            Some("Result") if args.returns.is_none() => {
+                Some("this `Result` may be an `Err` variant, which should be handled".to_string())
+            }
+            Some("Result" | "Option") => Some(String::new()),
+            _ => None,
+        };
+    }
+    if sig.asyncness.is_some() && args.cancellation_safety.is_none() {
+        args.cancellation_safety = match config("CUTEDOGS_CANCELLATION_NOTICE").as_deref() {
+            None | Some("" | "0" | "false") => None,
+            Some("1" | "true") => Some(LitStr::new(
+                "Cancellation safety has not been assessed. Do not assume that dropping this future before it completes leaves things in a consistent state.",
+                proc_macro2::Span::call_site(),
+            )),
+            Some(notice) => Some(LitStr::new(notice, proc_macro2::Span::call_site())),
+        };
+    }
+    if args.auto_example {
+        let example = example_skeleton(&args, sig);
+        args.examples.push(example);
+    }
+
+    let types = signature_types(sig);
+    if !types.is_empty() {
+        if let (Some(""), Some(returns)) = (args.must_use.as_deref(), args.returns.as_ref()) {
+            args.must_use = Some(returns.value());
+        }
+        for param in &mut args.params {
+            param.desc = LitStr::new(&link_types(&param.desc.value(), &types), param.desc.span());
+        }
+        if let Some(returns) = args.returns.as_mut() {
+            *returns = LitStr::new(&link_types(&returns.value(), &types), returns.span());
+        }
+    }
+    render_docs(&args)
+}
+
+fn signature_types(sig: &Signature) -> Vec<(String, String)> {
+    let mut tokens = TokenStream2::new();
+    for input in &sig.inputs {
+        if let FnArg::Typed(arg) = input {
+            arg.ty.to_tokens(&mut tokens);
+        }
+    }
+    sig.output.to_tokens(&mut tokens);
+    let generics = sig
+        .generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect::<Vec<_>>();
+    let mut types = vec![];
+    collect_type_paths(tokens, &generics, &mut types);
+    types
+}
+
+fn collect_type_paths(
+    tokens: TokenStream2,
+    generics: &[String],
+    types: &mut Vec<(String, String)>,
+) {
+    let mut path = Vec::<String>::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) => {
+                path.push(ident.to_string());
+                let separator = matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':');
+                if separator {
+                    tokens.next();
+                    tokens.next();
+                    continue;
+                }
+            }
+            TokenTree::Group(group) => collect_type_paths(group.stream(), generics, types),
+            _ => {}
+        }
+        if let Some(name) = path.last() {
+            let is_type = name.starts_with(|c: char| c.is_ascii_uppercase())
+                && name != "Self"
+                && !(path.len() == 1 && generics.contains(name));
+            if is_type && !types.iter().any(|(known, _)| known == name) {
+                types.push((name.clone(), path.join("::")));
+            }
+        }
+        path.clear();
+    }
+}
+
+fn link_types(text: &str, types: &[(String, String)]) -> String {
+    let mut linked = text.to_string();
+    for (name, path) in types {
+        let code = format!("`{}`", name);
+        let link = match name == path {
+            true => format!("[{}]", code),
+            false => format!("[{}]({})", code, path),
+        };
+        let mut rest = linked.as_str();
+        let mut rewritten = String::new();
+        while let Some(index) = rest.find(&code) {
+            let already_linked = rest[..index].ends_with('[');
+            rewritten.push_str(&rest[..index]);
+            rewritten.push_str(if already_linked { &code } else { &link });
+            rest = &rest[index + code.len()..];
+        }
+        rewritten.push_str(rest);
+        linked = rewritten;
+    }
+    linked
+}
+
+fn example_skeleton(args: &DocArgs, sig: &Signature) -> Example {
+    let generics = sig
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let mut concrete = true;
+    let type_of = |ty: &Type| -> Option<String> {
+        let tokens = concrete_tokens(ty.to_token_stream(), args.self_ty.as_ref(), &generics)?;
+        Some(tidy_tokens(&tokens))
+    };
+
+    let mut lines = vec![];
+    let mut call_args = vec![];
+    let mut receiver = None;
+    for (index, input) in sig.inputs.iter().enumerate() {
+        match input {
+            FnArg::Receiver(recv) => receiver = Some(recv),
+            FnArg::Typed(arg) => {
+                let name = match &*arg.pat {
+                    Pat::Ident(pat) => pat.ident.to_string().trim_start_matches('_').to_string(),
+                    _ => format!("arg{}", index),
+                };
+                match type_of(&arg.ty) {
+                    Some(ty) => lines.push(format!("# let {}: {} = todo!();", name, ty)),
+                    None => {
+                        concrete = false;
+                        lines.push(format!("# let {} = todo!();", name));
+                    }
+                }
+                call_args.push(name);
+            }
+        }
+    }
+
+    let self_ty = args.self_ty.as_ref().map(tidy_tokens);
+    let call = format!("{}({})", sig.ident, call_args.join(", "));
+    let call = match (receiver, self_ty) {
+        (Some(recv), Some(self_ty)) if recv.colon_token.is_none() => {
+            let binding = match (&recv.reference, &recv.mutability) {
+                (Some(_), Some(_)) => "mut value",
+                _ => "value",
+            };
+            lines.insert(0, format!("# let {}: {} = todo!();", binding, self_ty));
+            format!("value.{}", call)
+        }
+        (Some(_), _) => {
+            concrete = false;
+            lines.insert(0, "# let value = todo!();".to_string());
+            format!("value.{}", call)
+        }
+        (None, Some(self_ty))
+            if self_ty
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == ':') =>
+        {
+            format!("{}::{}", self_ty, call)
+        }
+        (None, Some(self_ty)) => format!("<{}>::{}", self_ty, call),
+        (None, None) => call,
+    };
+    let call = match sig.asyncness {
+        Some(_) => format!("{}.await", call),
+        None => call,
+    };
+    let call = match sig.unsafety {
+        Some(_) => format!("unsafe {{ {} }}", call),
+        None => call,
+    };
+    let call = match &sig.output {
+        ReturnType::Default => format!("{};", call),
+        ReturnType::Type(..) => format!("let result = {};", call),
+    };
+    lines.push(call);
+    if sig.asyncness.is_some() {
+        lines.insert(0, "# async fn example() {".to_string());
+        lines.push("# }".to_string());
+    }
+
+    Example {
+        title: None,
+        fence: if concrete && reachable_from_doctests(&sig.ident) {
+            "no_run"
+        } else {
+            "ignore"
+        },
+        code: lines.join("\n"),
+        generated: true,
+    }
+}
+
+fn reachable_from_doctests(name: &Ident) -> bool {
+    let Some(file) = proc_macro::Span::call_site()
+        .local_file()
+        .and_then(|file| std::path::absolute(file).ok())
+    else {
+        return false;
+    };
+    let src =
+        std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join("src");
+    let Ok(relative) = file.strip_prefix(&src) else {
+        return false;
+    };
+    let mut modules = relative
+        .with_extension("")
+        .iter()
+        .map(|part| part.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if modules.last().is_some_and(|last| last == "mod") {
+        modules.pop();
+    }
+    if modules == ["lib"] {
+        modules.clear();
+    }
+
+    let read_items = |path: &std::path::Path| {
+        let file = std::fs::read_to_string(path).ok()?;
+        Some(syn::parse_file(&file).ok()?.items)
+    };
+    let mut dir = src.clone();
+    let mut parent = src.join("lib.rs");
+    for module in &modules {
+        let Some(items) = read_items(&parent) else {
+            return false;
+        };
+        let public = items.iter().any(|item| {
+            matches!(item, Item::Mod(item_mod) if item_mod.ident == module
+                && item_mod.content.is_none()
+                && matches!(item_mod.vis, Visibility::Public(_)))
+        });
+        if !public {
+            return false;
+        }
+        let Some(path) = [
+            dir.join(module).with_extension("rs"),
+            dir.join(module).join("mod.rs"),
+        ]
+        .into_iter()
+        .find(|path| path.exists()) else {
+            return false;
+        };
+        parent = path;
+        dir = dir.join(module);
+    }
+
+    let Some(items) = read_items(&file) else {
+        return false;
+    };
+    let mut found = Vec::new();
+    find_public_path(&items, name, true, &mut found);
+    !found.is_empty() && found.iter().all(|public| *public)
+}
+
+fn find_public_path(items: &[Item], name: &Ident, public: bool, found: &mut Vec<bool>) {
+    let is_public = |vis: &Visibility| matches!(vis, Visibility::Public(_));
+    for item in items {
+        match item {
+            Item::Fn(item_fn) if item_fn.sig.ident == *name => {
+                found.push(public && is_public(&item_fn.vis))
+            }
+            Item::Impl(item_impl) => {
+                let self_public = match &*item_impl.self_ty {
+                    Type::Path(path) => path.path.segments.last().is_some_and(|segment| {
+                        items.iter().any(|item| match item {
+                            Item::Struct(ItemStruct { ident, vis, .. })
+                            | Item::Enum(ItemEnum { ident, vis, .. })
+                            | Item::Union(ItemUnion { ident, vis, .. })
+                            | Item::Type(ItemType { ident, vis, .. }) => {
+                                *ident == segment.ident && is_public(vis)
+                            }
+                            _ => false,
+                        })
+                    }),
+                    _ => false,
+                };
+                for item in &item_impl.items {
+                    if let ImplItem::Fn(method) = item {
+                        if method.sig.ident == *name {
+                            let method_public =
+                                item_impl.trait_.is_some() || is_public(&method.vis);
+                            found.push(public && self_public && method_public);
+                        }
+                    }
+                }
+            }
+            Item::Trait(item_trait) => {
+                for item in &item_trait.items {
+                    if matches!(item, TraitItem::Fn(method) if method.sig.ident == *name) {
+                        found.push(public && is_public(&item_trait.vis));
+                    }
+                }
+            }
+            Item::Mod(item_mod) => {
+                if let Some((_, items)) = item_mod.content.as_ref() {
+                    find_public_path(items, name, public && is_public(&item_mod.vis), found);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn concrete_tokens(
+    tokens: TokenStream2,
+    self_ty: Option<&Type>,
+    generics: &[Ident],
+) -> Option<TokenStream2> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut concrete = TokenStream2::new();
+    let mut index = 0;
+    while let Some(token) = tokens.get(index) {
+        match token {
+            TokenTree::Group(group) => {
+                let stream = concrete_tokens(group.stream(), self_ty, generics)?;
+                let mut replaced = proc_macro2::Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                concrete.extend([TokenTree::Group(replaced)]);
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                let lifetime = tokens.get(index + 1)?;
+                if lifetime.to_string() == "static" {
+                    concrete.extend([token.clone(), lifetime.clone()]);
+                } else {
+                    concrete.extend(quote! { '_ });
+                }
+                index += 1;
+            }
+            TokenTree::Ident(ident) if ident == "impl" || generics.contains(ident) => return None,
+            TokenTree::Ident(ident) if ident == "Self" => {
+                concrete.extend(self_ty?.to_token_stream())
+            }
+            _ => concrete.extend([token.clone()]),
+        }
+        index += 1;
+    }
+    Some(concrete)
+}
+
+fn summarize_name(name: &Ident) -> String {
+    let name = name.to_string();
+    let words = name
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let Some((verb, rest)) = words.split_first() else {
+        return name;
+    };
+    let rest = rest.join(" ");
+    let sentence = match *verb {
+        "new" if rest.is_empty() => "Creates a new value".to_string(),
+        "new" | "with" => format!("Creates a new value with {}", rest),
+        "is" => format!("Returns whether it is {}", rest),
+        "has" => format!("Returns whether it has {}", rest),
+        "to" | "into" => format!("Converts into {}", rest),
+        "as" => format!("Borrows as {}", rest),
+        "try" => format!("Tries to {}", rest),
+        _ => {
+            let verb = if verb.ends_with(['s', 'x', 'z'])
+                || verb.ends_with("ch")
+                || verb.ends_with("sh")
+            {
+                format!("{}es", verb)
+            } else if verb.len() > 1
+                && verb.ends_with('y')
+                && !verb[..verb.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u'])
+            {
+                format!("{}ies", &verb[..verb.len() - 1])
+            } else {
+                format!("{}s", verb)
+            };
+            let article = match rest.chars().next() {
+                None => "",
+                _ if rest.ends_with('s')
+                    || rest.starts_with("the ")
+                    || rest.starts_with("all ") =>
+                {
+                    ""
+                }
+                Some('a' | 'e' | 'i' | 'o' | 'u') => "an ",
+                Some(_) => "a ",
+            };
+            format!("{} {}{}", verb, article, rest)
+                .trim_end()
+                .to_string()
+        }
+    };
+    let mut chars = sentence.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => sentence,
+    }
+}
+
+fn render_generic_docs(args: &DocArgs, generics: &Generics) -> Vec<Attribute> {
+    let mut args = args.clone();
+    args.item_generics = Some(generics.clone());
+    render_docs(&args)
+}
+
+fn render_docs(args: &DocArgs) -> Vec<Attribute> {
+    let mut doc_parts = vec![];
+
+    if args.merge.as_deref() == Some("append") && !args.handwritten.is_empty() {
+        doc_parts.extend(args.handwritten.iter().cloned());
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+    if args.is_unimplemented {
+        let (summary, returns) = if let Some(reason) = args.unimplemented_reason.as_ref() {
+            (
+                format!("⚠️ **NOT IMPLEMENTED** - {}", reason),
+                "This function will panic with `unimplemented!()` when called".to_string(),
+            )
+        } else {
+            (
+                "⚠️ **NOT IMPLEMENTED** - This function is not yet implemented".to_string(),
+                "This function will panic with `unimplemented!()` when called".to_string(),
+            )
+        };
+
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "⚠️ **WARNING: NOT IMPLEMENTED**"] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = #summary]});
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = #returns]});
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+    if let Some(reason) = args.todo_body.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "🚧 **WORK IN PROGRESS**"] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        if !reason.is_empty() {
+            let reason_msg = format!("🚧 {}", reason);
+            doc_parts.push(parse_quote! { #[doc = #reason_msg] });
+            doc_parts.push(parse_quote! { #[doc = ""] });
+        }
+        let panic_msg = "This function will panic with `todo!()` when called";
+        doc_parts.push(parse_quote! { #[doc = #panic_msg] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+    if args.stub_default {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "⚠️ **WARNING: PLACEHOLDER IMPLEMENTATION**"] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let stub_msg =
+            "This function currently returns `Default::default()` without doing any work";
+        doc_parts.push(parse_quote! { #[doc = #stub_msg] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+    if let (Some(deprecated), Some(deprecated_since)) = (args.deprecated.as_ref(), args.deprecated_since.as_ref()) {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "⚠️ **DEPRECATED**"] });
+        let msg = format!("**Deprecated since {}:** {}", deprecated_since, deprecated);
+        push_lines(&mut doc_parts, &msg);
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    } else if let Some(deprecated) = args.deprecated.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "⚠️ **DEPRECATED**"] });
+        push_lines(&mut doc_parts, deprecated);
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    } else if let Some(deprecated_since) = args.deprecated_since.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "⚠️ **DEPRECATED**"] });
+        let msg = format!("**Deprecated since:** {}", deprecated_since);
+        push_lines(&mut doc_parts, &msg);
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    } else if args.deprecated_bare {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "⚠️ **DEPRECATED**"] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+    if let Some(migration) = args.migration.as_ref() {
+        let migration = format!("**Migration:** {}", link_code_spans(migration));
+        push_lines(&mut doc_parts, &migration);
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+    if args.hidden {
+        doc_parts.push(parse_quote! { #[doc(hidden)] });
+    }
+
+    if let Some(must_use) = args.must_use.as_ref() {
+        let must_use = LitStr::new(must_use, args.span_of("must_use"));
+        match (must_use.value().is_empty(), args.returns.as_ref()) {
+            (false, _) => doc_parts.push(parse_quote! { #[must_use = #must_use] }),
+            (true, Some(returns)) => doc_parts.push(parse_quote! { #[must_use = #returns] }),
+            (true, None) => doc_parts.push(parse_quote! { #[must_use] }),
+        }
+    }
+
+    if args.deprecated_attr != Some(false) {
+        let note = args
+            .deprecated
+            .as_ref()
+            .map(|note| LitStr::new(note, args.span_of("deprecated")));
+        let since = args
+            .deprecated_since
+            .as_ref()
+            .map(|since| LitStr::new(since, args.span_of("deprecated_since")));
+        match (note, since) {
+            (Some(note), Some(since)) => {
+                doc_parts.push(parse_quote! { #[deprecated(since = #since, note = #note)] })
+            }
+            (Some(note), None) => doc_parts.push(parse_quote! { #[deprecated(note = #note)] }),
+            (None, Some(since)) => doc_parts.push(parse_quote! { #[deprecated(since = #since)] }),
+            (None, None) => {}
+        }
+    }
+
+    if let Some(stability) = args.stability.as_ref() {
+        let banner = match stability.as_str() {
+            "experimental" => "🧪 **EXPERIMENTAL** — may change in a patch release",
+            "unstable" => "🚧 **UNSTABLE** — may change in a minor release",
+            _ => "✅ **STABLE** — covered by semver guarantees",
+        };
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = #banner] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+    }
+
+
+    if let Some(summary) = args.summary.as_ref() {
+        push_lines(&mut doc_parts, summary);
+    }
+
+    if let Some(since) = args.since.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let since_msg = format!("**Since:** {}", since);
+        doc_parts.push(parse_quote! { #[doc = #since_msg] });
+    }
+
+    if let Some(msrv) = args.msrv.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let msrv_msg = format!("**MSRV:** {}", msrv);
+        doc_parts.push(parse_quote! { #[doc = #msrv_msg] });
+    }
+
+    if let Some(units) = args.units.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let units_msg = format!("**Units:** {}", units);
+        doc_parts.push(parse_quote! { #[doc = #units_msg] });
+    }
+
+    if let Some(tracking_issue) = args.tracking_issue.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let tracking_msg = format!("**Tracking issue:** {}", issue_link(tracking_issue));
+        doc_parts.push(parse_quote! { #[doc = #tracking_msg] });
+    }
+
+    if let Some(feature) = args.feature.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let feature_msg = format!("**Available on crate feature `{}` only.**", feature);
+        doc_parts.push(parse_quote! { #[doc = #feature_msg] });
+    }
+
+    if let Some(cfg) = args.cfg.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let cfg_msg = format!("**Available on:** `cfg({})`", cfg.value());
+        doc_parts.push(parse_quote! { #[doc = #cfg_msg] });
+    }
+
+    if let Some(doc_cfg) = doc_cfg(args) {
+        doc_parts.push(doc_cfg);
+    }
+
+    if let Some(deterministic) = args.deterministic.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let deterministic_msg = format!("**Deterministic:** {}", deterministic);
+        doc_parts.push(parse_quote! { #[doc = #deterministic_msg] });
+    }
+
+    if let Some(idempotent) = args.idempotent.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let idempotent_msg = format!("**Idempotent:** {}", idempotent);
+        doc_parts.push(parse_quote! { #[doc = #idempotent_msg] });
+    }
+
+    let generics = match args.item_generics.as_ref() {
+        Some(generics) if args.auto_generics => generic_bounds(generics)
+            .into_iter()
+            .map(|(name, bounds)| {
+                let desc = args
+                    .generics
+                    .iter()
+                    .find(|param| param.name.value() == name)
+                    .map_or_else(|| "TODO: document".to_string(), |param| param.desc.value());
+                match bounds {
+                    Some(bounds) => format!("* `{}`: `{}` - {}", name, bounds, desc),
+                    None => format!("* `{}` - {}", name, desc),
+                }
+            })
+            .collect::<Vec<_>>(),
+        _ => args
+            .generics
+            .iter()
+            .map(|param| format!("* `{}` - {}", param.name.value(), param.desc.value()))
+            .collect(),
+    };
+    if !generics.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Type Parameters"] });
+        for generic_doc in &generics {
+            doc_parts.push(parse_quote! { #[doc = #generic_doc] });
+        }
+    }
+
+    if !args.lifetimes.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Lifetimes"] });
+        for param in &args.lifetimes {
+            let lifetime_doc = format!("* `{}` - {}", param.name.value(), param.desc.value());
+            doc_parts.push(parse_quote! { #[doc = #lifetime_doc] });
+        }
+    }
+
+    let params: Vec<(String, Option<String>, String)> = match args.signature.as_ref() {
+        Some(sig) if args.auto_params => sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(typed) => Some(typed),
+                FnArg::Receiver(_) => None,
+            })
+            .map(|typed| {
+                let name = match &*typed.pat {
+                    Pat::Ident(pat) => pat.ident.to_string(),
+                    pat => tidy_tokens(pat),
+                };
+                let desc = args
+                    .params
+                    .iter()
+                    .find(|param| param.name.value() == name)
+                    .map_or_else(|| "TODO: document".to_string(), |param| param.desc.value());
+                (name, Some(tidy_tokens(&typed.ty)), desc)
+            })
+            .collect(),
+        _ => args
+            .params
+            .iter()
+            .map(|param| (param.name.value(), None, param.desc.value()))
+            .collect(),
+    };
+    if !params.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Parameters"] });
+        for (name, ty, desc) in &params {
+            let unit = unit_of(args, name);
+            let name = match ty {
+                Some(ty) => format!("`{}`: `{}`", name, ty),
+                None => format!("`{}`", name),
+            };
+            let param_doc = match unit {
+                Some(unit) => format!("* {} - {} ({})", name, desc, unit),
+                None => format!("* {} - {}", name, desc),
+            };
+            doc_parts.push(parse_quote! { #[doc = #param_doc] });
+        }
+    }
+
+    if !args.default_values.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Defaults"] });
+        for default in &args.default_values {
+            let default_doc = format!("* `{}` - {}", default.name.value(), default.desc.value());
+            doc_parts.push(parse_quote! { #[doc = #default_doc] });
+        }
+    }
+
+    if !args.typed_fields.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Fields"] });
+        for (field, desc) in &args.typed_fields {
+            let field_doc = format!("* `{}` - {}", field, desc);
+            doc_parts.push(parse_quote! { #[doc = #field_doc] });
+        }
+    }
+
+    let returns = match (
+        args.returns.as_ref().map(LitStr::value),
+        unit_of(args, "returns"),
+    ) {
+        (Some(returns), Some(unit)) => Some(format!("{} ({})", returns, unit)),
+        (Some(returns), None) => Some(returns),
+        (None, Some(unit)) => Some(format!("A value in {}.", unit)),
+        (None, None) => None,
+    };
+    let returns = match (returns, args.signature.as_ref().map(|sig| &sig.output)) {
+        (Some(returns), Some(ReturnType::Type(_, ty))) if args.returns.is_some() => {
+            Some(format!("Returns `{}` — {}", tidy_tokens(ty), returns))
+        }
+        (returns, _) => returns,
+    };
+    if let Some(returns) = returns.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Returns"] });
+        push_lines(&mut doc_parts, returns);
+    }
+
+    let error_type = args.signature.as_ref().and_then(|sig| {
+        let error = result_error(&sig.output)?;
+        let is_generic = sig
+            .generics
+            .type_params()
+            .any(|param| matches!(error, Type::Path(path) if path.path.is_ident(&param.ident)));
+        let name = tidy_tokens(error);
+        Some(match error {
+            Type::Path(path) if !is_generic && path.qself.is_none() => {
+                let mut target = path.path.clone();
+                for segment in &mut target.segments {
+                    segment.arguments = PathArguments::None;
+                }
+                match tidy_tokens(&target) {
+                    target if target == name => format!("Fails with [`{}`].", name),
+                    target => format!("Fails with [`{}`]({}).", name, target),
+                }
+            }
+            _ => format!("Fails with `{}`.", name),
+        })
+    });
+    if args.errors.is_some() || !args.error_variants.is_empty() || error_type.is_some() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Errors"] });
+        if let Some(error_type) = error_type.as_ref() {
+            doc_parts.push(parse_quote! { #[doc = #error_type] });
+            if args.errors.is_some() {
+                doc_parts.push(parse_quote! { #[doc = ""] });
+            }
+        }
+        if let Some(errors) = args.errors.as_ref() {
+            push_lines(&mut doc_parts, errors);
+        }
+        for error in &args.error_variants {
+            let error_doc = format!("* [`{}`] - {}", error.name.value(), error.desc.value());
+            doc_parts.push(parse_quote! { #[doc = #error_doc] });
+        }
+    }
+
+    if !args.examples.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        if args.examples.len() == 1 {
+            doc_parts.push(parse_quote! { #[doc = "# Example"] });
+        } else {
+            doc_parts.push(parse_quote! { #[doc = "# Examples"] });
+        }
+        let prelude = args
+            .example_use_crate
+            .as_ref()
+            .map(|name| match name.as_str() {
+                "" => std::env::var("CARGO_CRATE_NAME").unwrap_or_default(),
+                name => name.replace('-', "_"),
+            });
+        for Example {
+            title,
+            fence,
+            code,
+            generated,
+        } in &args.examples
+        {
+            if let Some(title) = title {
+                doc_parts.push(parse_quote! { #[doc = ""] });
+                let title_doc = format!("## {}", title);
+                doc_parts.push(parse_quote! { #[doc = #title_doc] });
+            }
+            doc_parts.push(parse_quote! { #[doc = ""] });
+            let fence_doc = format!("```{}", fence);
+            doc_parts.push(parse_quote! { #[doc = #fence_doc] });
+            if *generated {
+                doc_parts.push(parse_quote! { #[doc = concat!("# use ", module_path!(), "::*;")] });
+            } else if let Some(name) = prelude.as_ref().filter(|_| *fence != "ignore") {
+                let use_doc = format!("# use {}::*;", name);
+                doc_parts.push(parse_quote! { #[doc = #use_doc] });
+            }
+            doc_parts.push(parse_quote! { #[doc = #code] });
+            doc_parts.push(parse_quote! { #[doc = "```"] });
+        }
+    }
+
+    if let Some(panics) = args.panics.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Panics"] });
+        push_lines(&mut doc_parts, panics);
+    }
+
+    if let Some(side_effects) = args.side_effects.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Side Effects"] });
+        push_lines(&mut doc_parts, side_effects);
+    }
+
+    if let [edge_case] = args.edge_cases.as_slice() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Edge Cases"] });
+        doc_parts.push(parse_quote! { #[doc = #edge_case] });
+    } else if !args.edge_cases.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Edge Cases"] });
+        for edge_case in &args.edge_cases {
+            let edge_case_doc = format!("* {}", edge_case);
+            doc_parts.push(parse_quote! { #[doc = #edge_case_doc] });
+        }
+    }
+
+    if !args.environment.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Environment"] });
+        for var in &args.environment {
+            let var_doc = format!("* `{}` - {}", var.name.value(), var.desc.value());
+            doc_parts.push(parse_quote! { #[doc = #var_doc] });
+        }
+    }
+
+    if let Some(safety) = args.safety.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Safety"] });
+        push_lines(&mut doc_parts, safety);
+    }
+
+    if let Some(security) = args.security.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Security Considerations"] });
+        push_lines(&mut doc_parts, security);
+    }
+
+    if let Some(abi) = args.abi.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# ABI"] });
+        doc_parts.push(parse_quote! { #[doc = #abi] });
+    }
+
+    if let Some(ownership) = args.ownership.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Ownership"] });
+        push_lines(&mut doc_parts, ownership);
+    }
+
+    if let Some(complexity) = args.complexity.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Complexity"] });
+        push_lines(&mut doc_parts, complexity);
+    }
+
+    if let Some(algorithm) = args.algorithm.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Algorithm"] });
+        let algorithm_doc = link_citations(algorithm, &args.references);
+        push_lines(&mut doc_parts, &algorithm_doc);
+    }
+
+    if let Some(performance) = args.performance.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Performance"] });
+        push_lines(&mut doc_parts, performance);
+    }
+
+    if let Some(memory) = args.memory.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Memory Usage"] });
+        push_lines(&mut doc_parts, memory);
+    }
+
+    if let Some(thread_safety) = args.thread_safety.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Thread Safety"] });
+        push_lines(&mut doc_parts, thread_safety);
+    }
+
+    if let Some(locking) = args.locking.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Locking"] });
+        push_lines(&mut doc_parts, locking);
+    }
+
+    if let Some(blocking) = args.blocking.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Blocking"] });
+        let blocking_msg = format!(
+            "⚠️ **This function blocks:** {}. Avoid calling it directly from async code.",
+            blocking.trim_end_matches('.')
+        );
+        push_lines(&mut doc_parts, &blocking_msg);
+    }
+
+    if let Some(cancellation_safety) = args.cancellation_safety.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Cancellation Safety"] });
+        push_lines(&mut doc_parts, &cancellation_safety.value());
+    }
+
+    if let Some(platform) = args.platform.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Platform Support"] });
+        for name in platform.value().split(',').map(|s| s.trim()) {
+            let platform_doc = format!("* {}", name);
+            doc_parts.push(parse_quote! { #[doc = #platform_doc] });
+        }
+    }
+
+    if let Some(see_also) = args.see_also.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# See Also"] });
+
+        for func in see_also.split(',').map(|s| s.trim()) {
+            let link_doc = format!("* [`{}`]", func);
+            doc_parts.push(parse_quote! { #[doc = #link_doc] });
+        }
+    }
+
+    if !args.references.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# References"] });
+        for reference in &args.references {
+            let reference_doc = format!("* {}", reference_link(reference));
+            doc_parts.push(parse_quote! { #[doc = #reference_doc] });
+        }
+    }
+
+    if let Some(invariants) = args.invariants.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Invariants"] });
+        push_lines(&mut doc_parts, invariants);
+    }
+
+    if let Some(preconditions) = args.preconditions.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Preconditions"] });
+        push_lines(&mut doc_parts, preconditions);
+    }
+
+    if let Some(postconditions) = args.postconditions.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Postconditions"] });
+        push_lines(&mut doc_parts, postconditions);
+    }
+
+    if let Some(warning) = args.warning.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Warning"] });
+        let warning_msg = format!("> 🚨 **Warning:** {}", warning);
+        push_lines(&mut doc_parts, &warning_msg);
+    }
+
+    if let [note] = args.notes.as_slice() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Note"] });
+        let note_msg = format!("⚠️ {}", note);
+        doc_parts.push(parse_quote! { #[doc = #note_msg] });
+    } else if !args.notes.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Note"] });
+        for note in &args.notes {
+            let note_msg = format!("* ⚠️ {}", note);
+            doc_parts.push(parse_quote! { #[doc = #note_msg] });
+        }
+    }
+
+    if let Some(todo) = args.todo.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# TODO"] });
+        push_lines(&mut doc_parts, todo);
+    }
+
+    for section in &args.sections {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        let heading = format!("# {}", section.name.value());
+        doc_parts.push(parse_quote! { #[doc = #heading] });
+        let body = &section.desc;
+        doc_parts.push(parse_quote! { #[doc = #body] });
+    }
+
+    if !args.history.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# History"] });
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "| Version | Change |"] });
+        doc_parts.push(parse_quote! { #[doc = "|---------|--------|"] });
+        for entry in &args.history {
+            let row = format!(
+                "| {} | {} |",
+                entry.name.value().replace('|', "\\|"),
+                entry.desc.value().replace('|', "\\|")
+            );
+            doc_parts.push(parse_quote! { #[doc = #row] });
+        }
+    }
+
+    if !args.keywords.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Keywords"] });
+        let keywords_doc = args
+            .keywords
+            .iter()
+            .map(|keyword| format!("`{}`", keyword.value()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        doc_parts.push(parse_quote! { #[doc = #keywords_doc] });
+        for keyword in &args.keywords {
+            let span = keyword.span();
+            doc_parts.push(parse_quote_spanned! {span=> #[doc(alias = #keyword)] });
+        }
+    }
+
+    if let Some(authors) = args.authors.as_ref() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.push(parse_quote! { #[doc = "# Credits"] });
+        doc_parts.push(parse_quote! { #[doc = #authors] });
+    }
+
+    if args.merge.as_deref() == Some("prepend") && !args.handwritten.is_empty() {
+        doc_parts.push(parse_quote! { #[doc = ""] });
+        doc_parts.extend(args.handwritten.iter().cloned());
+    }
+
+    interpolate(args, doc_parts)
+}
+
+fn interpolate(args: &DocArgs, doc_parts: Vec<Attribute>) -> Vec<Attribute> {
+    let fn_name = args.signature.as_ref().map(|sig| sig.ident.to_string());
+    let mut in_code = false;
+    doc_parts
+        .into_iter()
+        .map(|attr| {
+            let Meta::NameValue(meta) = &attr.meta else {
+                return attr;
+            };
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) = &meta.value
+            else {
+                return attr;
+            };
+            let is_doc = meta.path.is_ident("doc");
+            let text = lit.value();
+            if is_doc && text.trim_start().starts_with("```") {
+                in_code = !in_code;
+            }
+            if in_code || !text.contains('{') {
+                return attr;
+            }
+            let path = &meta.path;
+            if is_doc {
+                let value = interpolate_text(&text, fn_name.as_deref(), lit.span());
+                parse_quote! { #[doc = #value] }
+            } else {
+                let text = fill_placeholders(&text, fn_name.as_deref());
+                let lit = LitStr::new(&text, lit.span());
+                parse_quote! { #[#path = #lit] }
+            }
+        })
+        .collect()
+}
+
+fn fill_placeholders(text: &str, fn_name: Option<&str>) -> String {
+    let placeholders = [
+        ("{crate_name}", std::env::var("CARGO_CRATE_NAME").ok()),
+        ("{crate_version}", std::env::var("CARGO_PKG_VERSION").ok()),
+        ("{fn_name}", fn_name.map(String::from)),
+    ];
+    let mut text = text.to_string();
+    for (placeholder, value) in &placeholders {
+        if let Some(value) = value {
+            text = text.replace(placeholder, value);
+        }
+    }
+    text
+}
+
+fn interpolate_text(text: &str, fn_name: Option<&str>, span: Span) -> Expr {
+    let text = fill_placeholders(text, fn_name);
+    let parts = text
+        .split("{module_path}")
+        .map(|part| LitStr::new(part, span))
+        .collect::<Vec<_>>();
+    match parts.split_first() {
+        Some((first, rest)) if !rest.is_empty() => {
+            parse_quote! { ::core::concat!(#first #(, ::core::module_path!(), #rest)*) }
+        }
+        _ => Expr::Lit(ExprLit {
+            attrs: Vec::new(),
+            lit: Lit::Str(LitStr::new(&text, span)),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn semver(version: &str) -> Semver {
+        parse_semver(version).unwrap()
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(parse_version("1.70"), Some((1, 70, 0)));
+        assert_eq!(parse_version("1.70.2"), Some((1, 70, 2)));
+        assert_eq!(parse_version("1"), Some((1, 0, 0)));
+        assert_eq!(parse_version("1.70.0.1"), None);
+        assert_eq!(parse_version("1.x"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn orders_prereleases_before_releases() {
+        assert!(semver("1.0.0-alpha") < semver("1.0.0-alpha.1"));
+        assert!(semver("1.0.0-alpha.1") < semver("1.0.0-beta"));
+        assert!(semver("1.0.0-beta.2") < semver("1.0.0-beta.11"));
+        assert!(semver("1.0.0-rc.1") < semver("1.0.0"));
+        assert!(semver("1.0.0+build.5") == semver("1.0.0"));
+        assert!(parse_semver("1.0").is_none());
+    }
+
+    #[test]
+    fn bounds_since_by_the_next_minor_release() {
+        let current = semver("1.2.3");
+        assert!(since_allowed(&semver("0.9.0"), &current));
+        assert!(since_allowed(&semver("1.2.3"), &current));
+        assert!(since_allowed(&semver("1.2.4"), &current));
+        assert!(since_allowed(&semver("1.3.0-rc.1"), &current));
+        assert!(since_allowed(&semver("1.3.0"), &current));
+        assert!(!since_allowed(&semver("1.3.1"), &current));
+        assert!(!since_allowed(&semver("1.9.0"), &current));
+        assert!(!since_allowed(&semver("2.0.0"), &current));
+
+        let current = semver("0.4.1");
+        assert!(since_allowed(&semver("0.5.0"), &current));
+        assert!(!since_allowed(&semver("0.6.0"), &current));
+    }
+
+    #[test]
+    fn checks_since_against_the_crate_version() {
+        let lit = LitStr::new("0.0.1", Span::call_site());
+        assert!(check_since(&lit, &semver("0.0.1")).is_ok());
+        let lit = LitStr::new("9.0.0", Span::call_site());
+        let error = check_since(&lit, &semver("9.0.0")).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("newer than this crate's version"));
+    }
+
+    #[test]
+    fn checks_msrv() {
+        let error = check_msrv(&LitStr::new("one.seventy", Span::call_site())).unwrap_err();
+        assert!(error.to_string().starts_with("expected an MSRV"));
+        assert!(check_msrv(&LitStr::new("9.0", Span::call_site())).is_ok());
+        let error = check_msrv(&LitStr::new("1.0", Span::call_site())).unwrap_err();
+        assert!(error.to_string().contains("older than the crate-wide MSRV"));
+    }
+
+    #[test]
+    fn measures_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("summary", "summary"), 0);
+        assert_eq!(edit_distance("sumary", "summary"), 1);
+        assert_eq!(edit_distance("retruns", "returns"), 2);
+        assert_eq!(edit_distance("abc", ""), 3);
+    }
+
+    #[test]
+    fn suggests_close_fields() {
+        assert_eq!(suggest_field("sumary"), Some("summary"));
+        assert_eq!(suggest_field("retruns"), Some("returns"));
+        assert_eq!(suggest_field("exampel"), Some("example"));
+        assert_eq!(suggest_field("colour"), None);
+    }
+
+    #[test]
+    fn summarizes_names() {
+        let summarize = |name| summarize_name(&Ident::new(name, Span::call_site()));
+        assert_eq!(summarize("new"), "Creates a new value");
+        assert_eq!(
+            summarize("with_capacity"),
+            "Creates a new value with capacity"
+        );
+        assert_eq!(summarize("is_empty"), "Returns whether it is empty");
+        assert_eq!(summarize("to_string"), "Converts into string");
+        assert_eq!(summarize("push_item"), "Pushes an item");
+        assert_eq!(summarize("copy_bytes"), "Copies bytes");
+        assert_eq!(summarize("load_config"), "Loads a config");
+        assert_eq!(summarize("clear"), "Clears");
+    }
+
+    #[test]
+    fn flags_only_link_shaped_brackets() {
+        assert!(!has_broken_link("a value in [0, 1)"));
+        assert!(!has_broken_link("see [`Vec`] and [docs](https://docs.rs)"));
+        assert!(!has_broken_link("`[` alone in code"));
+        assert!(has_broken_link("see [`Vec` for more"));
+        assert!(has_broken_link("see docs](https://docs.rs)"));
+    }
+}
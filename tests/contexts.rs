@@ -0,0 +1,134 @@
+use cutedogs::{document, Document, Documented};
+
+#[document(summary = "Counts to two", example_file = "tests/fixtures/example.rs")]
+fn free_fn() -> u32 {
+    2
+}
+
+struct Counter(u32);
+
+impl Counter {
+    #[document(summary = "Adds one", example_file = "tests/fixtures/example.rs")]
+    fn add_one(&self) -> u32 {
+        self.0 + 1
+    }
+
+    #[document(summary = concat!("Starting value for ", env!("CARGO_PKG_NAME")))]
+    const START: u32 = 3;
+
+    #[document(summary = include_str!("fixtures/summary.txt"))]
+    const fn start() -> u32 {
+        Self::START
+    }
+}
+
+extern "C" {
+    #[document(summary = include_str!("fixtures/summary.txt"))]
+    fn abs(value: i32) -> i32;
+}
+
+#[test]
+fn tracks_files_on_free_fns() {
+    assert_eq!(free_fn(), 2);
+}
+
+#[test]
+fn tracks_files_on_methods() {
+    assert_eq!(Counter(1).add_one(), 2);
+}
+
+#[test]
+fn tracks_files_on_associated_items() {
+    assert_eq!(Counter::START, 3);
+    assert_eq!(Counter::start(), 3);
+}
+
+#[test]
+fn skips_tracking_on_foreign_items() {
+    assert_eq!(unsafe { abs(-3) }, 3);
+}
+
+#[document(summary = "Something that can be counted")]
+trait Countable {
+    #[document(summary = "Returns the count", see_also = "free_fn", params = {"scale": "Multiplier"})]
+    fn count(&self, scale: u32) -> u32;
+}
+
+impl Countable for Counter {
+    fn count(&self, scale: u32) -> u32 {
+        self.0 * scale
+    }
+}
+
+#[test]
+fn checks_required_trait_methods() {
+    assert_eq!(Counter(2).count(3), 6);
+}
+
+#[document(summary = "Counts the old way")]
+#[derive(Document)]
+#[deprecated]
+struct OldCounter;
+
+#[test]
+#[allow(deprecated)]
+fn renders_bare_deprecated() {
+    assert!(<OldCounter as Documented>::DOC.contains("**DEPRECATED**"));
+}
+
+#[document(summary = "Lives in {module_path}", since = "0.1.0")]
+#[derive(Document)]
+struct Located;
+
+#[test]
+fn interpolates_module_path_in_derived_docs() {
+    assert_eq!(Located::summary(), Some("Lives in contexts"));
+    assert!(Located::DOC.starts_with("Lives in contexts\n"));
+    assert!(Located::DOC.ends_with("**Since:** 0.1.0"));
+}
+
+mod shapes {
+    use cutedogs::document;
+
+    #[document(summary = "Something with an area")]
+    pub trait Shape {
+        #[document(summary = "Returns the area", returns = "The area in square units")]
+        fn area(&self) -> u32;
+    }
+}
+
+mod square {
+    use super::shapes::Shape;
+    use cutedogs::document;
+
+    pub struct Square(pub u32);
+
+    #[document(summary = "Squares are shapes")]
+    impl Shape for Square {
+        #[document(inherit)]
+        fn area(&self) -> u32 {
+            self.0 * self.0
+        }
+    }
+}
+
+mod rectangle {
+    use cutedogs::document;
+
+    pub struct Rectangle(pub u32, pub u32);
+
+    #[document(summary = "Rectangles are shapes")]
+    impl crate::shapes::Shape for Rectangle {
+        #[document(inherit = "crate::shapes::Shape::area")]
+        fn area(&self) -> u32 {
+            self.0 * self.1
+        }
+    }
+}
+
+#[test]
+fn inherits_trait_docs_across_modules() {
+    use shapes::Shape;
+    assert_eq!(square::Square(3).area(), 9);
+    assert_eq!(rectangle::Rectangle(2, 3).area(), 6);
+}
@@ -0,0 +1,2 @@
+let total = 1 + 1;
+assert_eq!(total, 2);
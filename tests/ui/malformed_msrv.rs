@@ -0,0 +1,8 @@
+use cutedogs::document;
+
+#[document(summary = "Adds two numbers", msrv = "1.seventy")]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use cutedogs::document;
+
+#[document(summary = "Reads the value behind a pointer")]
+pub unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+fn main() {}